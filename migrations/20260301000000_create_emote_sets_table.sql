@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS emote_sets (
+    id SERIAL PRIMARY KEY,
+    seven_tv_set_id TEXT NOT NULL,
+    folder_name TEXT NOT NULL UNIQUE,
+    set_name TEXT NOT NULL,
+    last_synced_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    emote_count INTEGER DEFAULT 0
+);
@@ -0,0 +1,4 @@
+-- Trigram index so ILIKE '%term%' searches on emote names can use an index
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_stickers_emote_name_trgm ON stickers USING GIN (emote_name gin_trgm_ops);
@@ -0,0 +1,3 @@
+-- Keep the image variant chosen at sync time so saved responses match fresh ones
+ALTER TABLE stickers ADD COLUMN IF NOT EXISTS scale INTEGER;
+ALTER TABLE stickers ADD COLUMN IF NOT EXISTS mime TEXT;
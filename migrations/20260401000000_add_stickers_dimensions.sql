@@ -0,0 +1,3 @@
+-- Pixel size of the stored image so clients can reserve layout space
+ALTER TABLE stickers ADD COLUMN IF NOT EXISTS width INTEGER;
+ALTER TABLE stickers ADD COLUMN IF NOT EXISTS height INTEGER;
@@ -0,0 +1,2 @@
+-- 7TV zero-width overlay emotes, so sticker consumers can leave them out
+ALTER TABLE stickers ADD COLUMN IF NOT EXISTS zero_width BOOLEAN DEFAULT false;
@@ -0,0 +1,2 @@
+-- When a trending sync wrote the row, so readers can tell how fresh the folder is
+ALTER TABLE stickers ADD COLUMN IF NOT EXISTS synced_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+-- Stored file size so clients can check platform upload limits before downloading
+ALTER TABLE stickers ADD COLUMN IF NOT EXISTS size_bytes INTEGER;
@@ -0,0 +1,2 @@
+-- X-Request-Id of the request that started the job, to match reports to logs
+ALTER TABLE sync_jobs ADD COLUMN IF NOT EXISTS request_id TEXT;
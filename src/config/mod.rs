@@ -1,5 +1,7 @@
 use serde::Deserialize;
 use std::env;
+use std::net::SocketAddr;
+use crate::models::ImageFormat;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -9,14 +11,62 @@ pub struct Config {
     pub redis_password: String,
     pub redis_db: i32,
     pub redis_url: String,
+    pub storage_backend: StorageBackendKind,
     pub azure_conn_str: String,
     pub container_name: String,
+    /// Directory blobs are written to with `STORAGE_BACKEND=local`.
+    pub local_storage_dir: String,
+    /// URL local blobs are served under; defaults to this server's `/emotes` route.
+    pub local_storage_base_url: String,
+    /// S3-compatible endpoint such as `http://minio:9000`; empty means AWS.
+    pub s3_endpoint: String,
+    pub s3_bucket: String,
+    pub s3_region: String,
+    pub s3_access_key_id: String,
+    pub s3_secret_access_key: String,
     pub cache_ttl: u64,
     pub trending_cache_ttl: u64,
+    /// Number of emotes downloaded/uploaded concurrently during a batch.
+    /// Valid range is 1..=50; out-of-range values are clamped.
+    pub emote_download_concurrency: usize,
+    /// Best-first image formats used when picking which variant to store.
+    /// Formats left out rank after the listed ones.
+    pub image_format_preference: Vec<ImageFormat>,
+    /// Upper bound on client-supplied `limit` for searches, trending and syncs.
+    pub max_emote_limit: i32,
+    /// How emote names are turned into blob file names.
+    pub filename_strategy: FilenameStrategy,
+    /// 7TV GraphQL endpoint; override to point at a proxy or a mock server.
+    pub seventv_api_url: String,
+    /// Timeout in seconds for 7TV GraphQL requests.
+    pub gql_timeout_secs: u64,
+    /// Timeout in seconds for each CDN image download, so a stuck download
+    /// frees its batch slot quickly.
+    pub download_timeout_secs: u64,
     pub api_title: String,
     pub api_description: String,
     pub api_version: String,
     pub database_url: String,
+    pub database_max_connections: u32,
+    pub admin_api_key: String,
+    /// Bearer token accepted on admin routes, alongside `admin_api_key`.
+    pub admin_token: String,
+    /// Requests per minute allowed per client IP on the search endpoints; 0 disables the limit.
+    pub rate_limit_per_minute: u32,
+    /// Take the client IP from `X-Forwarded-For`. Only enable behind a trusted proxy.
+    pub trust_proxy_headers: bool,
+    /// Comma-separated origins allowed by CORS (`*` for any). Empty disables CORS.
+    pub cors_allowed_origins: String,
+    /// Seconds to wait on SIGTERM/SIGINT for in-flight requests and sync jobs
+    /// before exiting anyway.
+    pub shutdown_drain_secs: u64,
+    /// Requests slower than this many seconds are logged at warn.
+    pub slow_request_secs: u64,
+    /// Address to listen on, e.g. `127.0.0.1:8000`. Empty means `0.0.0.0:PORT`.
+    pub bind_addr: String,
+    /// PEM certificate and key; TLS is served only when both are set.
+    pub tls_cert_path: String,
+    pub tls_key_path: String,
 }
 
 impl Config {
@@ -33,8 +83,18 @@ impl Config {
                 .parse()
                 .unwrap_or(0),
             redis_url: env::var("REDIS_URL").unwrap_or_default(),
+            storage_backend: StorageBackendKind::parse(&env::var("STORAGE_BACKEND").unwrap_or_default()),
             azure_conn_str: env::var("AZURE_CONNECTION_STRING").unwrap_or_default(),
             container_name: env::var("CONTAINER_NAME").unwrap_or_else(|_| "emotes".to_string()),
+            local_storage_dir: env::var("LOCAL_STORAGE_DIR").unwrap_or_else(|_| "./storage".to_string()),
+            local_storage_base_url: env::var("LOCAL_STORAGE_BASE_URL").unwrap_or_else(|_| {
+                format!("http://localhost:{}/emotes", env::var("PORT").unwrap_or_else(|_| "8000".to_string()))
+            }),
+            s3_endpoint: env::var("S3_ENDPOINT").unwrap_or_default(),
+            s3_bucket: env::var("S3_BUCKET").unwrap_or_default(),
+            s3_region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            s3_access_key_id: env::var("S3_ACCESS_KEY_ID").unwrap_or_default(),
+            s3_secret_access_key: env::var("S3_SECRET_ACCESS_KEY").unwrap_or_default(),
             cache_ttl: env::var("CACHE_TTL")
                 .unwrap_or_else(|_| "3600".to_string())
                 .parse()
@@ -43,11 +103,160 @@ impl Config {
                 .unwrap_or_else(|_| "900".to_string())
                 .parse()
                 .unwrap_or(900),
+            emote_download_concurrency: env::var("EMOTE_DOWNLOAD_CONCURRENCY")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse::<usize>()
+                .unwrap_or(5)
+                .clamp(1, 50),
+            image_format_preference: parse_format_preference(
+                &env::var("IMAGE_FORMAT_PREFERENCE").unwrap_or_default(),
+            ),
+            filename_strategy: FilenameStrategy::parse(&env::var("FILENAME_STRATEGY").unwrap_or_default()),
+            max_emote_limit: env::var("MAX_EMOTE_LIMIT")
+                .unwrap_or_else(|_| "200".to_string())
+                .parse::<i32>()
+                .unwrap_or(200)
+                .max(1),
+            seventv_api_url: env::var("SEVENTV_API_URL")
+                .unwrap_or_else(|_| "https://api.7tv.app/v4/gql".to_string()),
+            gql_timeout_secs: env::var("GQL_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            download_timeout_secs: env::var("DOWNLOAD_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
             api_title: env::var("API_TITLE").unwrap_or_else(|_| "7TV Emote API".to_string()),
             api_description: env::var("API_DESCRIPTION")
                 .unwrap_or_else(|_| "API for fetching and storing 7TV emotes".to_string()),
             api_version: env::var("API_VERSION").unwrap_or_else(|_| "1.0.0".to_string()),
             database_url: env::var("DATABASE_URL").expect("DATABASE_URL must be set"),
+            database_max_connections: env::var("DATABASE_MAX_CONNECTIONS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            admin_api_key: env::var("ADMIN_API_KEY").unwrap_or_default(),
+            admin_token: env::var("ADMIN_TOKEN").unwrap_or_default(),
+            rate_limit_per_minute: env::var("RATE_LIMIT_PER_MINUTE")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            trust_proxy_headers: env::var("TRUST_PROXY_HEADERS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            cors_allowed_origins: env::var("CORS_ALLOWED_ORIGINS").unwrap_or_default(),
+            shutdown_drain_secs: env::var("SHUTDOWN_DRAIN_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            slow_request_secs: env::var("SLOW_REQUEST_SECS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            bind_addr: env::var("BIND_ADDR").unwrap_or_default(),
+            tls_cert_path: env::var("TLS_CERT_PATH").unwrap_or_default(),
+            tls_key_path: env::var("TLS_KEY_PATH").unwrap_or_default(),
+        }
+    }
+
+    /// The address to listen on: `BIND_ADDR` if set, else `0.0.0.0:PORT`.
+    pub fn socket_addr(&self) -> Result<SocketAddr, String> {
+        let bind_addr = self.bind_addr.trim();
+        if !bind_addr.is_empty() {
+            return bind_addr
+                .parse()
+                .map_err(|e| format!("Invalid BIND_ADDR {:?} (expected host:port): {}", bind_addr, e));
+        }
+        let port = self.port.parse::<u16>().unwrap_or(8000);
+        Ok(SocketAddr::from(([0, 0, 0, 0], port)))
+    }
+
+    /// `(cert, key)` paths when TLS is configured. Setting only one of them is
+    /// an error rather than a silent fallback to plain HTTP.
+    pub fn tls_paths(&self) -> Result<Option<(&str, &str)>, String> {
+        match (self.tls_cert_path.trim(), self.tls_key_path.trim()) {
+            ("", "") => Ok(None),
+            ("", _) => Err("TLS_KEY_PATH is set but TLS_CERT_PATH is not".to_string()),
+            (_, "") => Err("TLS_CERT_PATH is set but TLS_KEY_PATH is not".to_string()),
+            (cert, key) => Ok(Some((cert, key))),
+        }
+    }
+}
+
+/// `STORAGE_BACKEND`: where emote files are stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum StorageBackendKind {
+    /// Azure Blob Storage via `AZURE_CONNECTION_STRING`.
+    #[default]
+    Azure,
+    /// Files under `LOCAL_STORAGE_DIR`, for development and small setups.
+    Local,
+    /// AWS S3 or an S3-compatible store such as MinIO, via `S3_*`.
+    S3,
+}
+
+impl StorageBackendKind {
+    /// Unknown or empty values fall back to Azure.
+    fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "azure" => Self::Azure,
+            "local" => Self::Local,
+            "s3" => Self::S3,
+            _ => {
+                tracing::warn!("Invalid STORAGE_BACKEND '{}', using azure", value);
+                Self::Azure
+            }
+        }
+    }
+}
+
+/// `FILENAME_STRATEGY`: how emote names are cleaned up for file names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum FilenameStrategy {
+    /// ASCII letters and digits plus `.-_ `; anything else becomes `_`.
+    Ascii,
+    /// Lowercase ASCII words joined by single dashes.
+    Slug,
+    /// Like `Ascii` but keeps Unicode letters and digits. This is what file
+    /// names have always looked like, so existing blobs keep their names.
+    #[default]
+    PreserveUnicode,
+}
+
+impl FilenameStrategy {
+    /// Unknown or empty values fall back to the default.
+    fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" => Self::default(),
+            "ascii" => Self::Ascii,
+            "slug" => Self::Slug,
+            "preserve_unicode" => Self::PreserveUnicode,
+            _ => {
+                tracing::warn!("Invalid FILENAME_STRATEGY '{}', using preserve_unicode", value);
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Parses a comma-separated list like `avif,webp,gif,png`. An empty value,
+/// an unknown format or a repeated format falls back to the default order.
+fn parse_format_preference(value: &str) -> Vec<ImageFormat> {
+    if value.trim().is_empty() {
+        return ImageFormat::DEFAULT_PREFERENCE.to_vec();
+    }
+
+    let mut formats = Vec::new();
+    for part in value.split(',') {
+        match ImageFormat::parse(part) {
+            Some(format) if !formats.contains(&format) => formats.push(format),
+            _ => {
+                tracing::warn!("Invalid IMAGE_FORMAT_PREFERENCE '{}', using the default order", value);
+                return ImageFormat::DEFAULT_PREFERENCE.to_vec();
+            }
         }
     }
+    formats
 }
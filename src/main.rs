@@ -4,8 +4,13 @@ mod routes;
 mod services;
 
 use crate::config::Config;
+use axum_server::tls_rustls::RustlsConfig;
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use sqlx::postgres::PgPoolOptions;
 
@@ -16,14 +21,46 @@ async fn main() {
         .init();
 
     let cfg = Config::from_env();
-    let port = cfg.port.parse::<u16>().unwrap_or(8000);
+
+    // Bad listener settings should stop startup before anything connects
+    let addr = match cfg.socket_addr() {
+        Ok(addr) => addr,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let tls = match cfg.tls_paths() {
+        Ok(Some((cert, key))) => match RustlsConfig::from_pem_file(cert, key).await {
+            Ok(tls) => Some(tls),
+            Err(e) => {
+                tracing::error!("Failed to load TLS certificate {} / key {}: {}", cert, key, e);
+                std::process::exit(1);
+            }
+        },
+        Ok(None) => None,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if cfg.admin_token.is_empty() && cfg.admin_api_key.is_empty() {
+        tracing::warn!("!!! Neither ADMIN_TOKEN nor ADMIN_API_KEY is set: admin routes are UNPROTECTED !!!");
+    }
     
     // Database connection
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
+    let pool = match PgPoolOptions::new()
+        .max_connections(cfg.database_max_connections)
         .connect(&cfg.database_url)
         .await
-        .expect("Failed to connect to Postgres");
+    {
+        Ok(pool) => pool,
+        Err(e) => {
+            tracing::error!("Failed to connect to Postgres (check DATABASE_URL): {}", e);
+            std::process::exit(1);
+        }
+    };
 
     // Run migrations
     sqlx::migrate!()
@@ -31,9 +68,16 @@ async fn main() {
         .await
         .expect("Failed to run migrations");
 
-    let storage = Arc::new(services::storage::StorageService::new(&cfg));
-    let cache = Arc::new(services::cache::CacheService::new(&cfg));
-    let seventv = Arc::new(services::seventv::SevenTVService::new(Arc::clone(&storage)));
+    let metrics = Arc::new(services::metrics::Metrics::new());
+    let storage = Arc::new(services::storage::StorageService::new(&cfg, Arc::clone(&metrics)));
+    let cache = Arc::new(services::cache::CacheService::new(&cfg, Arc::clone(&metrics)));
+    let shutdown = CancellationToken::new();
+    let seventv = Arc::new(services::seventv::SevenTVService::new(
+        &cfg, Arc::clone(&storage), Arc::clone(&metrics), shutdown.clone(),
+    ));
+
+    // Slow WebSocket clients skip old updates once this many are queued
+    let (trending_updates, _) = broadcast::channel(16);
 
     let app_state = AppState {
         config: cfg,
@@ -41,17 +85,102 @@ async fn main() {
         cache,
         seventv,
         db: pool,
+        metrics,
+        trending_updates,
+        shutdown: shutdown.clone(),
+        in_flight: Arc::new(AtomicUsize::new(0)),
     };
 
     let shared_state = Arc::new(app_state);
+    let drain_timeout = Duration::from_secs(shared_state.config.shutdown_drain_secs);
+    let in_flight = Arc::clone(&shared_state.in_flight);
 
     let app = routes::create_router(shared_state);
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
-    tracing::info!("Starting server on {}", addr);
-    
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    let listener = match tokio::net::TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::error!("Failed to bind {}: {}", addr, e);
+            std::process::exit(1);
+        }
+    };
+    let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+    let stop_accepting = shutdown.clone();
+    let server = match tls {
+        Some(tls) => {
+            tracing::info!("Starting server on https://{}", addr);
+            let listener = listener.into_std().expect("listener was just bound");
+            let handle = axum_server::Handle::new();
+            let shutdown_handle = handle.clone();
+            tokio::spawn(async move {
+                stop_accepting.cancelled().await;
+                // The drain timeout below bounds how long this may take
+                shutdown_handle.graceful_shutdown(None);
+            });
+            tokio::spawn(async move {
+                axum_server::from_tcp_rustls(listener, tls).handle(handle).serve(make_service).await
+            })
+        }
+        None => {
+            tracing::info!("Starting server on http://{}", addr);
+            tokio::spawn(async move {
+                axum::serve(listener, make_service)
+                    .with_graceful_shutdown(async move { stop_accepting.cancelled().await })
+                    .await
+            })
+        }
+    };
+
+    shutdown_signal().await;
+    let draining = in_flight.load(Ordering::SeqCst);
+    tracing::info!("Shutting down, draining {} in-flight requests (timeout {:?})", draining, drain_timeout);
+    // Stops accepting connections and tells sync loops to stop between emotes
+    shutdown.cancel();
+
+    let drained = tokio::time::timeout(drain_timeout, async {
+        if let Ok(Err(e)) = server.await {
+            tracing::error!("Server error while draining: {}", e);
+        }
+        // Background sync jobs outlive their request, so wait for them too
+        while in_flight.load(Ordering::SeqCst) > 0 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await;
+
+    match drained {
+        Ok(()) => tracing::info!("Drained {} in-flight requests, server stopped", draining),
+        Err(_) => tracing::warn!(
+            "Drain timeout reached with {} of {} requests still in flight, stopping anyway",
+            in_flight.load(Ordering::SeqCst),
+            draining
+        ),
+    }
+}
+
+/// Resolves on Ctrl+C or SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
 
 pub struct AppState {
@@ -59,6 +188,29 @@ pub struct AppState {
     pub storage: Arc<services::storage::StorageService>,
     pub cache: Arc<services::cache::CacheService>,
     pub seventv: Arc<services::seventv::SevenTVService>,
-    pub db: sqlx::Pool<sqlx::Postgres>,
+    pub db: sqlx::PgPool,
+    pub metrics: Arc<services::metrics::Metrics>,
+    /// Serialized `SearchResponse` of each finished trending sync, fanned out to `/ws/trending`.
+    pub trending_updates: broadcast::Sender<Arc<String>>,
+    /// Cancelled on SIGTERM/SIGINT; long syncs check it between emotes.
+    pub shutdown: CancellationToken,
+    /// Requests and background sync jobs still running, waited on at shutdown.
+    pub in_flight: Arc<AtomicUsize>,
+}
+
+impl AppState {
+    /// Counts one unit of work as in flight until the guard is dropped.
+    pub fn track_in_flight(&self) -> InFlightGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard(Arc::clone(&self.in_flight))
+    }
+}
+
+pub struct InFlightGuard(Arc<AtomicUsize>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
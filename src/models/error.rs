@@ -0,0 +1,176 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use std::fmt;
+use utoipa::ToSchema;
+use crate::models::SearchResponse;
+use crate::routes::request_id;
+use crate::services::error::ServiceError;
+
+/// One rejected request field.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FieldError {
+    /// Name of the field as the client sent it (e.g. `limit`, `folderName`).
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self { field: field.to_string(), message: message.into() }
+    }
+}
+
+/// Body of every `ApiError`: the usual `SearchResponse` plus the ID of the
+/// failed request, to quote when reporting it.
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorResponse {
+    #[serde(flatten)]
+    pub response: SearchResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// Body of a 422 for invalid fields: the usual error body plus `errors`.
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidFieldsResponse {
+    #[serde(flatten)]
+    pub response: SearchResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub errors: Vec<FieldError>,
+}
+
+/// Error returned by handlers. Renders as the usual `SearchResponse` body
+/// with `success: false`, but with a status code matching the failure.
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound(String),
+    /// 7TV failed or returned something unusable.
+    Upstream(String),
+    Database(String),
+    Storage(String),
+    Validation(String),
+    /// Well-formed request whose parameters can't be used (422).
+    Unprocessable(String),
+    /// Like `Unprocessable`, but with each offending field listed. Build it
+    /// with `ApiError::invalid_fields`.
+    InvalidFields(String, Vec<FieldError>),
+    Unauthorized(String),
+    /// The server can't take the work right now (503): it is shutting down, or
+    /// the request needs storage and no backend is configured.
+    Unavailable(String),
+}
+
+impl ApiError {
+    /// A 422 listing `errors`; its message joins them for plain-text consumers.
+    pub fn invalid_fields(errors: Vec<FieldError>) -> Self {
+        let message = errors
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        ApiError::InvalidFields(message, errors)
+    }
+
+    pub fn invalid_field(field: &str, message: impl Into<String>) -> Self {
+        Self::invalid_fields(vec![FieldError::new(field, message)])
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Upstream(_) => StatusCode::BAD_GATEWAY,
+            ApiError::Database(_) | ApiError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Validation(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unprocessable(_) | ApiError::InvalidFields(..) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            ApiError::NotFound(msg)
+            | ApiError::Upstream(msg)
+            | ApiError::Database(msg)
+            | ApiError::Storage(msg)
+            | ApiError::Validation(msg)
+            | ApiError::Unprocessable(msg)
+            | ApiError::InvalidFields(msg, _)
+            | ApiError::Unauthorized(msg)
+            | ApiError::Unavailable(msg) => msg,
+        }
+    }
+
+    /// The `success: false` body this error renders as.
+    pub fn to_search_response(&self) -> SearchResponse {
+        SearchResponse {
+            success: false,
+            total_found: 0,
+            emotes: vec![],
+            message: Some(self.message().to_string()),
+            cached: Some(false),
+            processing_time: None,
+            page: None,
+            total_pages: None,
+            results_per_page: None,
+            has_next_page: None,
+            failed_count: None,
+            animated_count: None,
+            static_count: None,
+            synced_at: None,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let response = self.to_search_response();
+        let request_id = request_id::current();
+        match self {
+            ApiError::InvalidFields(_, errors) => {
+                (status, Json(InvalidFieldsResponse { response, request_id, errors })).into_response()
+            }
+            _ => (status, Json(ErrorResponse { response, request_id })).into_response(),
+        }
+    }
+}
+
+impl From<ServiceError> for ApiError {
+    fn from(e: ServiceError) -> Self {
+        ApiError::from(&e)
+    }
+}
+
+impl From<&ServiceError> for ApiError {
+    fn from(e: &ServiceError) -> Self {
+        match e {
+            ServiceError::NotFound => ApiError::NotFound(e.to_string()),
+            ServiceError::Storage(_) | ServiceError::Cache(_) => ApiError::Storage(e.to_string()),
+            ServiceError::Http(_) | ServiceError::Upstream { .. } | ServiceError::Parse(_) => {
+                ApiError::Upstream(e.to_string())
+            }
+        }
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(e: sqlx::Error) -> Self {
+        ApiError::Database(format!("Database error: {}", e))
+    }
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+mod error;
+pub use error::{ApiError, ErrorResponse, FieldError, InvalidFieldsResponse};
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EmoteResponse {
     pub file_name: String,
@@ -17,9 +21,57 @@ pub struct EmoteResponse {
     pub mime: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    /// Pixel size of the stored image variant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    /// 7TV zero-width overlay emote, meant to be drawn on top of the previous one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zero_width: Option<bool>,
+    /// The emote's page on 7tv.app.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_url: Option<String>,
+    /// Size of the stored file, or of the 7TV file for dry runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<i32>,
+}
+
+impl EmoteResponse {
+    pub fn seventv_page_url(emote_id: &str) -> String {
+        format!("https://7tv.app/emotes/{}", emote_id)
+    }
+}
+
+/// One image variant 7TV serves for an emote.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmoteVariant {
+    pub url: String,
+    pub mime: String,
+    pub scale: i32,
+    pub width: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub frame_count: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmoteVariantsResponse {
+    pub emote_id: String,
+    pub emote_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    pub variants: Vec<EmoteVariant>,
+    /// Index into `variants` of the image a default sync would store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_index: Option<usize>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchResponse {
     pub success: bool,
@@ -29,6 +81,8 @@ pub struct SearchResponse {
     pub message: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cached: Option<bool>,
+    /// Seconds spent serving this request, to the millisecond. Cache hits
+    /// report their own lookup time, not the time the cached result took.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub processing_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -39,22 +93,271 @@ pub struct SearchResponse {
     pub results_per_page: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub has_next_page: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_count: Option<i32>,
+    /// Breakdown of `emotes` by type, set on trending responses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animated_count: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub static_count: Option<i32>,
+    /// When the synced trending folder was last written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl SearchResponse {
+    /// Fills `animated_count` and `static_count` from the emotes' `animated`
+    /// flag; emotes without one count as static.
+    pub fn count_animation(&mut self) {
+        let animated = self.emotes.iter().filter(|e| e.animated == Some(true)).count() as i32;
+        self.animated_count = Some(animated);
+        self.static_count = Some(self.emotes.len() as i32 - animated);
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SearchRequest {
+    #[serde(default)]
     pub query: String,
+    /// Search several queries at once; takes precedence over `query` and
+    /// returns a `BatchSearchResponse` keyed by query.
+    pub queries: Option<Vec<String>>,
     #[serde(alias = "perPage")]
     pub limit: Option<i32>,
+    /// `any` (default), `animated` or `static`.
+    pub animation: Option<AnimationFilter>,
+    /// Deprecated: use `animation`. `true` is treated as `animation: "animated"`.
+    #[schema(deprecated)]
     pub animated_only: Option<bool>,
     pub page: Option<i32>,
+    /// Only keep emotes whose owner display name matches (case-insensitive).
+    pub owner: Option<String>,
+    /// Image format to store when 7TV offers it; falls back to the default
+    /// `IMAGE_FORMAT_PREFERENCE` order otherwise.
+    #[serde(alias = "preferredFormat")]
+    pub preferred_format: Option<ImageFormat>,
+    /// Largest image scale to store, 1 to 4; defaults to the largest available.
+    #[serde(alias = "preferredScale")]
+    pub preferred_scale: Option<i32>,
+    /// Only return emotes carrying these 7TV tags; see `tag_match`. With tags
+    /// set, `query` may be empty.
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub tags: Option<Vec<String>>,
+    /// Whether an emote needs any (default) or all of `tags`.
+    #[serde(alias = "tagMatch")]
+    pub tag_match: Option<TagMatch>,
+    /// Only return emotes named exactly `query`, still in 7TV ranking order.
+    #[serde(alias = "exactMatch")]
+    pub exact_match: Option<bool>,
+    /// With `exact_match`, compare names case-sensitively (default true).
+    #[serde(alias = "caseSensitive")]
+    pub case_sensitive: Option<bool>,
+    /// `top_all_time` (default), `trending_daily`, `trending_weekly`,
+    /// `trending_monthly` or `upload_date`.
+    #[serde(alias = "sortBy")]
+    pub sort_by: Option<String>,
+    /// Set to false to drop zero-width overlay emotes (default true).
+    #[serde(alias = "includeZeroWidth")]
+    pub include_zero_width: Option<bool>,
+}
+
+/// Result ordering for emote search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchSort {
+    #[default]
+    TopAllTime,
+    TrendingDaily,
+    TrendingWeekly,
+    TrendingMonthly,
+    UploadDate,
+}
+
+impl SearchSort {
+    pub const ALLOWED: &'static str = "top_all_time, trending_daily, trending_weekly, trending_monthly, upload_date";
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "top_all_time" => Some(SearchSort::TopAllTime),
+            "trending_daily" => Some(SearchSort::TrendingDaily),
+            "trending_weekly" => Some(SearchSort::TrendingWeekly),
+            "trending_monthly" => Some(SearchSort::TrendingMonthly),
+            "upload_date" => Some(SearchSort::UploadDate),
+            _ => None,
+        }
+    }
+
+    /// The 7TV GraphQL `SortBy` value.
+    pub fn as_gql(self) -> &'static str {
+        match self {
+            SearchSort::TopAllTime => "TOP_ALL_TIME",
+            SearchSort::TrendingDaily => "TRENDING_DAILY",
+            SearchSort::TrendingWeekly => "TRENDING_WEEKLY",
+            SearchSort::TrendingMonthly => "TRENDING_MONTHLY",
+            SearchSort::UploadDate => "UPLOAD_DATE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+    #[default]
+    #[serde(alias = "ANY")]
+    Any,
+    #[serde(alias = "ALL")]
+    All,
+}
+
+impl TagMatch {
+    /// The 7TV GraphQL enum value.
+    pub fn as_gql(self) -> &'static str {
+        match self {
+            TagMatch::Any => "ANY",
+            TagMatch::All => "ALL",
+        }
+    }
+}
+
+/// Which emotes to keep by animation; `any` leaves the 7TV filter unset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationFilter {
+    #[default]
+    Any,
+    Animated,
+    Static,
+}
+
+impl AnimationFilter {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnimationFilter::Any => "any",
+            AnimationFilter::Animated => "animated",
+            AnimationFilter::Static => "static",
+        }
+    }
+
+    /// Value for the `animated` GQL filter key, or `None` to omit it.
+    pub fn gql_animated(self) -> Option<bool> {
+        match self {
+            AnimationFilter::Any => None,
+            AnimationFilter::Animated => Some(true),
+            AnimationFilter::Static => Some(false),
+        }
+    }
+
+    /// Prefers `animation`; the deprecated `animated_only: true` maps to
+    /// `animated` and anything else to `any`.
+    pub fn resolve(animation: Option<Self>, animated_only: Option<bool>) -> Self {
+        animation.unwrap_or(if animated_only == Some(true) {
+            AnimationFilter::Animated
+        } else {
+            AnimationFilter::Any
+        })
+    }
+}
+
+/// Query string form of `SearchRequest` for `GET /api/search-emotes`.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    #[serde(alias = "query", default)]
+    pub q: String,
+    #[serde(alias = "perPage")]
+    pub limit: Option<i32>,
+    pub page: Option<i32>,
+    /// `any` (default), `animated` or `static`.
+    pub animation: Option<AnimationFilter>,
+    /// Deprecated: use `animation`.
+    #[serde(alias = "animated_only")]
+    #[param(deprecated)]
+    pub animated_only: Option<bool>,
+    pub owner: Option<String>,
+    pub preferred_format: Option<ImageFormat>,
+    pub preferred_scale: Option<i32>,
+    /// Comma-separated 7TV tags.
+    #[serde(default, deserialize_with = "comma_separated")]
+    #[param(value_type = Option<String>)]
+    pub tags: Option<Vec<String>>,
+    pub tag_match: Option<TagMatch>,
+    pub exact_match: Option<bool>,
+    pub case_sensitive: Option<bool>,
+    pub sort_by: Option<String>,
+    pub include_zero_width: Option<bool>,
+}
+
+impl From<SearchQuery> for SearchRequest {
+    fn from(q: SearchQuery) -> Self {
+        SearchRequest {
+            query: q.q,
+            queries: None,
+            limit: q.limit,
+            animation: q.animation,
+            animated_only: q.animated_only,
+            page: q.page,
+            owner: q.owner,
+            preferred_format: q.preferred_format,
+            preferred_scale: q.preferred_scale,
+            tags: q.tags,
+            tag_match: q.tag_match,
+            exact_match: q.exact_match,
+            case_sensitive: q.case_sensitive,
+            sort_by: q.sort_by,
+            include_zero_width: q.include_zero_width,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    Webp,
+    Gif,
+    Avif,
+    Png,
+}
+
+impl ImageFormat {
+    /// Order used to pick an image when the caller has no preference.
+    pub const DEFAULT_PREFERENCE: [ImageFormat; 4] =
+        [ImageFormat::Webp, ImageFormat::Gif, ImageFormat::Avif, ImageFormat::Png];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "webp" => Some(ImageFormat::Webp),
+            "gif" => Some(ImageFormat::Gif),
+            "avif" => Some(ImageFormat::Avif),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            ImageFormat::Webp => "image/webp",
+            ImageFormat::Gif => "image/gif",
+            ImageFormat::Avif => "image/avif",
+            ImageFormat::Png => "image/png",
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImageFormat::Webp => "webp",
+            ImageFormat::Gif => "gif",
+            ImageFormat::Avif => "avif",
+            ImageFormat::Png => "png",
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TrendingPeriod {
     #[serde(rename = "trending_daily")]
     Daily,
+    #[default]
     #[serde(rename = "trending_weekly")]
     Weekly,
     #[serde(rename = "trending_monthly")]
@@ -63,30 +366,199 @@ pub enum TrendingPeriod {
     AllTime,
 }
 
-impl Default for TrendingPeriod {
-    fn default() -> Self {
-        Self::Weekly
+impl TrendingPeriod {
+    pub const ALL: [TrendingPeriod; 4] =
+        [TrendingPeriod::Daily, TrendingPeriod::Weekly, TrendingPeriod::Monthly, TrendingPeriod::AllTime];
+
+    /// The period with this `id()`, if any.
+    pub fn from_id(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.id() == s)
+    }
+
+    /// The identifier clients pass as `period`.
+    pub fn id(self) -> &'static str {
+        match self {
+            TrendingPeriod::Daily => "trending_daily",
+            TrendingPeriod::Weekly => "trending_weekly",
+            TrendingPeriod::Monthly => "trending_monthly",
+            TrendingPeriod::AllTime => "popularity",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TrendingPeriod::Daily => "Trending today",
+            TrendingPeriod::Weekly => "Trending this week",
+            TrendingPeriod::Monthly => "Trending this month",
+            TrendingPeriod::AllTime => "Most popular of all time",
+        }
     }
+
+    /// The 7TV GraphQL `sortBy` value for this period.
+    pub fn sort_by(self) -> &'static str {
+        match self {
+            TrendingPeriod::Daily => "TRENDING_DAILY",
+            TrendingPeriod::Weekly => "TRENDING_WEEKLY",
+            TrendingPeriod::Monthly => "TRENDING_MONTHLY",
+            TrendingPeriod::AllTime => "TOP_ALL_TIME",
+        }
+    }
+}
+
+/// One entry of `GET /api/trending/periods`.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendingPeriodInfo {
+    /// Value to pass as `period`.
+    pub id: &'static str,
+    pub label: &'static str,
+    /// 7TV `sortBy` the period maps to.
+    pub sort_by: &'static str,
+    pub default: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrendingPeriodsResponse {
+    pub success: bool,
+    pub periods: Vec<TrendingPeriodInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SyncTrendingRequest {
     pub period: Option<String>,
     pub animated_only: Option<bool>,
     pub limit: Option<i32>,
+    /// Largest image scale to store (e.g. 1 or 2); defaults to the largest available.
+    #[serde(alias = "preferredScale")]
+    pub preferred_scale: Option<i32>,
+    /// Image format to store when available; see `SearchRequest::preferred_format`.
+    #[serde(alias = "preferredFormat")]
+    pub preferred_format: Option<ImageFormat>,
+    /// URL that receives a `SyncWebhookPayload` once the sync finishes.
+    #[serde(alias = "callbackUrl")]
+    pub callback_url: Option<String>,
+    /// Re-encode static WebP emotes as PNG. Adds a decode/encode pass per
+    /// emote on the server and produces larger files.
+    pub convert_to_png: Option<bool>,
+    /// Fetch from 7TV and return what would be synced without deleting,
+    /// downloading or saving anything. Returned emotes have empty `url` and `fileName`.
+    pub dry_run: Option<bool>,
+    /// Only sync trending emotes carrying at least one of these tags; see
+    /// `TrendingQuery::tags`. The folder then holds the tag-filtered set.
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Accepts a list either as a JSON array or as one comma-separated string,
+/// which is the only form a query string can carry.
+pub fn comma_separated<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        Some(OneOrMany::One(s)) => Some(s.split(',').map(str::to_string).collect()),
+        Some(OneOrMany::Many(v)) => Some(v),
+        None => None,
+    })
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncUserEmotesRequest {
-    pub user_id: String,
+    pub user_id: Option<String>,
+    /// Platform login (e.g. a Twitch username) resolved to a 7TV user ID when
+    /// `user_id` is not given.
+    pub platform_username: Option<String>,
+    /// Connection platform for `platform_username`; defaults to `TWITCH`.
+    pub platform: Option<String>,
     pub limit: Option<i32>,
     pub folder_name: String,
+    pub preferred_scale: Option<i32>,
+    pub preferred_format: Option<ImageFormat>,
+    pub callback_url: Option<String>,
+    pub convert_to_png: Option<bool>,
+    /// Same as `SyncTrendingRequest::dry_run`.
+    pub dry_run: Option<bool>,
+}
+
+/// Options for re-syncing a folder already in `users`; the 7TV user comes from the DB.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResyncUserRequest {
+    pub limit: Option<i32>,
+    pub preferred_scale: Option<i32>,
+    pub preferred_format: Option<ImageFormat>,
+    pub callback_url: Option<String>,
+    pub convert_to_png: Option<bool>,
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncEmoteSetRequest {
+    #[serde(alias = "setId")]
+    pub emote_set_id: String,
+    pub limit: Option<i32>,
+    pub folder_name: String,
+    pub preferred_scale: Option<i32>,
+    pub callback_url: Option<String>,
+    pub convert_to_png: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncWebhookPayload {
+    pub folder: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+    pub success: bool,
+    pub processed_count: i32,
+    pub failed_count: i32,
+    pub duration_secs: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// `X-Request-Id` of the request that started the sync.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 #[serde(rename_all = "camelCase")]
 pub struct SavedUserEmotesQuery {
     pub folder_name: String,
     pub limit: Option<i32>,
+    pub page: Option<i32>,
+    /// Rows to skip instead of `page`.
+    pub offset: Option<i64>,
+    /// Only return animated emotes.
+    pub animated_only: Option<bool>,
+    /// Only return static emotes.
+    pub static_only: Option<bool>,
+    /// Set to false to leave out zero-width overlay emotes (default true).
+    pub include_zero_width: Option<bool>,
+    /// Only return emotes whose stored file is at most this many bytes.
+    /// Rows synced before sizes were recorded are left out.
+    pub max_size_bytes: Option<i32>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct StickerSearchQuery {
+    /// Matched against emote names (substring, case-insensitive) and tags (exact).
+    pub q: String,
+    /// Restrict the search to one folder; searches every folder when omitted.
+    pub folder_name: Option<String>,
+    pub animated_only: Option<bool>,
+    pub limit: Option<i32>,
+    pub page: Option<i32>,
 }
@@ -0,0 +1,99 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use async_zip::{tokio::write::ZipFileWriter, Compression, ZipEntryBuilder};
+use std::sync::Arc;
+use tokio_util::io::ReaderStream;
+use crate::AppState;
+
+// Size of the in-memory pipe between the zip writer task and the response body
+const PIPE_CAPACITY: usize = 64 * 1024;
+
+/// Streams every blob under `{folder_name}/` (including `_metadata.json`) as a
+/// ZIP archive. Blobs are fetched one at a time and written straight into the
+/// response, so only a single emote is held in memory at once.
+#[utoipa::path(
+    get,
+    path = "/api/folders/{folder_name}/archive",
+    tag = "folders",
+    params(("folder_name" = String, Path, description = "Folder to archive")),
+    responses(
+        (status = 200, description = "ZIP of every emote in the folder", content_type = "application/zip"),
+        (status = 404, description = "Folder not found")
+    )
+)]
+pub async fn folder_archive_handler(
+    State(state): State<Arc<AppState>>,
+    Path(folder_name): Path<String>,
+) -> Response {
+    let prefix = format!("{}/", folder_name);
+
+    let blob_names = match state.storage.list_blobs(&prefix).await {
+        Ok(names) if !names.is_empty() => names,
+        Ok(_) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "success": false,
+                    "message": format!("No stored emotes found for folder {}", folder_name),
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to list blobs for {}: {:?}", folder_name, e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "success": false,
+                    "message": format!("Failed to list stored emotes: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let (reader, writer) = tokio::io::duplex(PIPE_CAPACITY);
+
+    tokio::spawn(async move {
+        let mut zip = ZipFileWriter::with_tokio(writer);
+
+        for blob_name in blob_names {
+            let data = match state.storage.get_blob_content(&blob_name).await {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::error!("Skipping blob {} in archive: {:?}", blob_name, e);
+                    continue;
+                }
+            };
+
+            let entry_name = blob_name.trim_start_matches(&prefix).to_string();
+            // Emote images are already compressed, so store them as-is
+            let entry = ZipEntryBuilder::new(entry_name.into(), Compression::Stored);
+            if let Err(e) = zip.write_entry_whole(entry, &data).await {
+                // The client most likely disconnected; stop producing the archive
+                tracing::warn!("Aborting archive for {}: {:?}", prefix, e);
+                return;
+            }
+        }
+
+        if let Err(e) = zip.close().await {
+            tracing::warn!("Failed to finalize archive for {}: {:?}", prefix, e);
+        }
+    });
+
+    let file_name = format!("{}.zip", folder_name.replace('/', "_"));
+
+    (
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)),
+        ],
+        Body::from_stream(ReaderStream::new(reader)),
+    )
+        .into_response()
+}
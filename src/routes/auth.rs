@@ -0,0 +1,52 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+use crate::config::Config;
+use crate::AppState;
+
+/// True when `headers` carry valid admin credentials, or when no admin
+/// credentials are configured at all.
+pub fn is_admin(config: &Config, headers: &HeaderMap) -> bool {
+    if config.admin_token.is_empty() && config.admin_api_key.is_empty() {
+        return true;
+    }
+
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let api_key = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok());
+
+    (!config.admin_token.is_empty() && bearer == Some(config.admin_token.as_str()))
+        || (!config.admin_api_key.is_empty() && api_key == Some(config.admin_api_key.as_str()))
+}
+
+/// Guards the admin routes. A request is let through when it carries either
+/// `Authorization: Bearer <ADMIN_TOKEN>` or `X-Api-Key: <ADMIN_API_KEY>`.
+/// With neither configured every request is allowed (a warning is logged at
+/// startup) so local development keeps working.
+pub async fn require_admin_key(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !is_admin(&state.config, request.headers()) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({
+                "success": false,
+                "message": "Missing or invalid admin credentials",
+            })),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
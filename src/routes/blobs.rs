@@ -0,0 +1,141 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+use crate::services::error::ServiceError;
+use crate::AppState;
+
+// File names embed the emote ID so content rarely changes; a resync that does
+// replace a file is still picked up through ETag revalidation
+const CACHE_CONTROL: &str = "public, max-age=604800";
+
+/// Rejects segments that could escape the folder once joined into a blob
+/// name. Encoded slashes are allowed in the folder for `trending/...` paths.
+fn is_safe_segment(segment: &str, allow_slash: bool) -> bool {
+    !segment.is_empty()
+        && !segment.contains('\\')
+        && !segment.chars().any(char::is_control)
+        && (allow_slash || !segment.contains('/'))
+        && segment.split('/').all(|part| !part.is_empty() && part != "." && part != "..")
+}
+
+/// Content type for a stored emote, falling back to the file extension when
+/// the blob was stored without a specific one.
+fn content_type_for(stored: &str, file_name: &str) -> String {
+    if !stored.is_empty() && stored != "application/octet-stream" {
+        return stored.to_string();
+    }
+    let ext = file_name.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("webp") => "image/webp",
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("avif") => "image/avif",
+        Some("json") => "application/json",
+        _ => "application/octet-stream",
+    }
+    .to_string()
+}
+
+/// True when any tag in an `If-None-Match` header matches `etag`.
+fn etag_matches(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let Ok(value) = if_none_match.to_str() else {
+        return false;
+    };
+    value.split(',').map(str::trim).any(|tag| {
+        tag == "*" || tag.trim_start_matches("W/").trim_matches('"') == etag
+    })
+}
+
+/// Serves a stored emote through the API so clients never need the Azure
+/// URL. Supports `If-None-Match` so repeat requests cost a 304.
+#[utoipa::path(
+    get,
+    path = "/emotes/{folder}/{file_name}",
+    tag = "emotes",
+    params(
+        ("folder" = String, Path, description = "Folder the emote was stored in"),
+        ("file_name" = String, Path, description = "Stored file name, e.g. `catJAM_abc123.webp`")
+    ),
+    responses(
+        (status = 200, description = "The emote image", content_type = "image/*"),
+        (status = 304, description = "Matches the client's `If-None-Match`"),
+        (status = 400, description = "Folder or file name tries to leave the folder"),
+        (status = 404, description = "No such stored emote")
+    )
+)]
+pub async fn serve_emote_handler(
+    State(state): State<Arc<AppState>>,
+    Path((folder, file_name)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Response {
+    if !is_safe_segment(&folder, true) || !is_safe_segment(&file_name, false) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "success": false,
+                "message": "Invalid folder or file name",
+            })),
+        )
+            .into_response();
+    }
+
+    let blob_name = format!("{}/{}", folder, file_name);
+
+    let info = match state.storage.get_blob_info(&blob_name).await {
+        Ok(info) => info,
+        Err(ServiceError::NotFound) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "success": false,
+                    "message": format!("Emote {} not found", blob_name),
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to read blob properties for {}: {:?}", blob_name, e);
+            return (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({
+                    "success": false,
+                    "message": format!("Failed to read stored emote: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let etag = format!("\"{}\"", info.etag);
+    if headers.get(header::IF_NONE_MATCH).is_some_and(|v| etag_matches(v, &info.etag)) {
+        return (
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag), (header::CACHE_CONTROL, CACHE_CONTROL.to_string())],
+        )
+            .into_response();
+    }
+
+    let body = match state.storage.stream_blob(&blob_name) {
+        Ok(stream) => Body::from_stream(stream),
+        Err(e) => {
+            tracing::error!("Failed to stream blob {}: {:?}", blob_name, e);
+            return StatusCode::BAD_GATEWAY.into_response();
+        }
+    };
+
+    (
+        [
+            (header::CONTENT_TYPE, content_type_for(&info.content_type, &file_name)),
+            (header::CONTENT_LENGTH, info.content_length.to_string()),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, CACHE_CONTROL.to_string()),
+        ],
+        body,
+    )
+        .into_response()
+}
@@ -0,0 +1,355 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Json,
+};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use crate::models::SyncUserEmotesRequest;
+use crate::AppState;
+
+// Keep 7TV load modest: user syncs already download emotes concurrently
+const MAX_PARALLEL_USER_SYNCS: usize = 3;
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncAllUsersRequest {
+    /// Only sync users whose last sync is older than this many days (or who
+    /// never finished one). Every user is synced when omitted.
+    pub stale_days: Option<i32>,
+    /// How many users to sync at once, 1 to 3 (default 1).
+    pub parallelism: Option<usize>,
+    /// List the folders that would be synced without syncing them.
+    pub dry_run: Option<bool>,
+    pub limit: Option<i32>,
+    pub preferred_scale: Option<i32>,
+    pub convert_to_png: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserSyncSummary {
+    folder_name: String,
+    seven_tv_id: String,
+    success: bool,
+    emote_count: i32,
+    failed_count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncAllUsersResponse {
+    success: bool,
+    dry_run: bool,
+    synced: usize,
+    failed: usize,
+    total_emotes_processed: i32,
+    users: Vec<UserSyncSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+// Entries run one after another, so cap how long a single request can take
+const MAX_BATCH_USERS: usize = 50;
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchUserEntry {
+    #[serde(alias = "user_id")]
+    pub user_id: String,
+    #[serde(alias = "folder_name")]
+    pub folder_name: String,
+    pub limit: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncUsersBatchRequest {
+    /// Users to sync, in order; at most 50.
+    pub users: Vec<BatchUserEntry>,
+    pub preferred_scale: Option<i32>,
+    pub convert_to_png: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchUserResult {
+    folder_name: String,
+    success: bool,
+    count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncUsersBatchResponse {
+    success: bool,
+    synced: usize,
+    failed: usize,
+    results: Vec<BatchUserResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+impl SyncUsersBatchResponse {
+    fn rejected(message: String) -> (StatusCode, Json<Self>) {
+        (StatusCode::BAD_REQUEST, Json(SyncUsersBatchResponse {
+            success: false,
+            synced: 0,
+            failed: 0,
+            results: vec![],
+            message: Some(message),
+        }))
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StaleUser {
+    seven_tv_id: String,
+    folder_name: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/sync-all-users",
+    request_body = SyncAllUsersRequest,
+    responses(
+        (status = 200, description = "Per-folder sync results", body = SyncAllUsersResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 500, description = "Could not load users", body = SyncAllUsersResponse),
+        (status = 503, description = "Storage not configured", body = SyncAllUsersResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn sync_all_users_handler(
+    State(state): State<Arc<AppState>>,
+    payload: Option<Json<SyncAllUsersRequest>>,
+) -> (StatusCode, Json<SyncAllUsersResponse>) {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let dry_run = payload.dry_run.unwrap_or(false);
+    let parallelism = payload.parallelism.unwrap_or(1).clamp(1, MAX_PARALLEL_USER_SYNCS);
+
+    // Fail once up front rather than once per user
+    if let (false, Err(e)) = (dry_run, super::ensure_storage(&state)) {
+        return (
+            e.status(),
+            Json(SyncAllUsersResponse {
+                success: false,
+                dry_run,
+                synced: 0,
+                failed: 0,
+                total_emotes_processed: 0,
+                users: vec![],
+                message: Some(e.to_string()),
+            }),
+        );
+    }
+
+    let users = sqlx::query_as::<_, StaleUser>(
+        r#"
+        SELECT seven_tv_id, folder_name FROM users
+        WHERE $1::INT IS NULL
+           OR last_synced_at IS NULL
+           OR last_synced_at < NOW() - make_interval(days => $1)
+        ORDER BY last_synced_at ASC NULLS FIRST
+        "#
+    )
+    .bind(payload.stale_days)
+    .fetch_all(&state.db)
+    .await;
+
+    let users = match users {
+        Ok(users) => users,
+        Err(e) => {
+            tracing::error!("Failed to load users for bulk sync: {:?}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(SyncAllUsersResponse {
+                    success: false,
+                    dry_run,
+                    synced: 0,
+                    failed: 0,
+                    total_emotes_processed: 0,
+                    users: vec![],
+                    message: Some(format!("Database error: {}", e)),
+                }),
+            );
+        }
+    };
+
+    if dry_run {
+        let users = users
+            .into_iter()
+            .map(|u| UserSyncSummary {
+                folder_name: u.folder_name,
+                seven_tv_id: u.seven_tv_id,
+                success: true,
+                emote_count: 0,
+                failed_count: 0,
+                message: None,
+            })
+            .collect::<Vec<_>>();
+        return (
+            StatusCode::OK,
+            Json(SyncAllUsersResponse {
+                success: true,
+                dry_run,
+                synced: 0,
+                failed: 0,
+                total_emotes_processed: 0,
+                message: Some(format!("Dry run: {} folders would be synced", users.len())),
+                users,
+            }),
+        );
+    }
+
+    tracing::info!("Bulk syncing {} user folders with parallelism {}", users.len(), parallelism);
+
+    let results: Vec<UserSyncSummary> = stream::iter(users)
+        .map(|user| {
+            let state = Arc::clone(&state);
+            let request = SyncUserEmotesRequest {
+                user_id: Some(user.seven_tv_id.clone()),
+                platform_username: None,
+                platform: None,
+                limit: payload.limit,
+                folder_name: user.folder_name.clone(),
+                preferred_scale: payload.preferred_scale,
+                preferred_format: None,
+                callback_url: None,
+                convert_to_png: payload.convert_to_png,
+                dry_run: None,
+            };
+            async move {
+                match super::sync_user_emotes(&state, request).await {
+                    Ok(response) => UserSyncSummary {
+                        folder_name: user.folder_name,
+                        seven_tv_id: user.seven_tv_id,
+                        success: true,
+                        emote_count: response.total_found,
+                        failed_count: response.failed_count.unwrap_or(0),
+                        message: None,
+                    },
+                    Err(e) => {
+                        tracing::error!("Bulk sync of {} failed: {}", user.folder_name, e);
+                        UserSyncSummary {
+                            folder_name: user.folder_name,
+                            seven_tv_id: user.seven_tv_id,
+                            success: false,
+                            emote_count: 0,
+                            failed_count: 0,
+                            message: Some(e.to_string()),
+                        }
+                    }
+                }
+            }
+        })
+        .buffer_unordered(parallelism)
+        .collect()
+        .await;
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    (
+        StatusCode::OK,
+        Json(SyncAllUsersResponse {
+            success: failed == 0,
+            dry_run,
+            synced: results.len() - failed,
+            failed,
+            total_emotes_processed: results.iter().map(|r| r.emote_count).sum(),
+            users: results,
+            message: None,
+        }),
+    )
+}
+
+/// Syncs a caller-supplied list of users one at a time, to stay within 7TV's
+/// rate limits. A failing user is recorded and the rest still run.
+#[utoipa::path(
+    post,
+    path = "/api/admin/sync-users-batch",
+    request_body = SyncUsersBatchRequest,
+    responses(
+        (status = 200, description = "Per-user sync results", body = SyncUsersBatchResponse),
+        (status = 400, description = "Empty or oversized batch", body = SyncUsersBatchResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 503, description = "Storage not configured", body = SyncUsersBatchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn sync_users_batch_handler(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<SyncUsersBatchRequest>,
+) -> (StatusCode, Json<SyncUsersBatchResponse>) {
+    if payload.users.is_empty() {
+        return SyncUsersBatchResponse::rejected("users must not be empty".to_string());
+    }
+    if payload.users.len() > MAX_BATCH_USERS {
+        return SyncUsersBatchResponse::rejected(format!("at most {} users are allowed per batch", MAX_BATCH_USERS));
+    }
+    if let Err(e) = super::ensure_storage(&state) {
+        let (_, body) = SyncUsersBatchResponse::rejected(e.to_string());
+        return (e.status(), body);
+    }
+
+    tracing::info!("Batch syncing {} users", payload.users.len());
+
+    let mut results = Vec::with_capacity(payload.users.len());
+    for user in payload.users {
+        let outcome = match super::validate_folder_name(&user.folder_name) {
+            Ok(()) => {
+                let request = SyncUserEmotesRequest {
+                    user_id: Some(user.user_id),
+                    platform_username: None,
+                    platform: None,
+                    limit: user.limit,
+                    folder_name: user.folder_name.clone(),
+                    preferred_scale: payload.preferred_scale,
+                    preferred_format: None,
+                    callback_url: None,
+                    convert_to_png: payload.convert_to_png,
+                    dry_run: None,
+                };
+                super::sync_user_emotes(&state, request).await
+            }
+            Err(e) => Err(e),
+        };
+
+        results.push(match outcome {
+            Ok(response) => BatchUserResult {
+                folder_name: user.folder_name,
+                success: true,
+                count: response.total_found,
+                error: None,
+            },
+            Err(e) => {
+                tracing::error!("Batch sync of {} failed: {}", user.folder_name, e);
+                BatchUserResult {
+                    folder_name: user.folder_name,
+                    success: false,
+                    count: 0,
+                    error: Some(e.to_string()),
+                }
+            }
+        });
+    }
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    (
+        StatusCode::OK,
+        Json(SyncUsersBatchResponse {
+            success: failed == 0,
+            synced: results.len() - failed,
+            failed,
+            results,
+            message: None,
+        }),
+    )
+}
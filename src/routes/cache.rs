@@ -0,0 +1,68 @@
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use crate::AppState;
+use crate::models::ApiError;
+
+const DEFAULT_PATTERN: &str = "emote_search:*";
+
+#[derive(Deserialize, Default, ToSchema)]
+pub struct ClearCacheRequest {
+    /// Redis glob pattern (e.g. `trending:*`); defaults to `emote_search:*` when omitted.
+    pub pattern: Option<String>,
+    /// Required for patterns that match every key (e.g. `*`).
+    pub confirm: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearCacheResponse {
+    success: bool,
+    pattern: String,
+    keys_removed: usize,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/cache/clear",
+    request_body = ClearCacheRequest,
+    responses(
+        (status = 200, description = "Matching keys removed", body = ClearCacheResponse),
+        (status = 400, description = "Empty pattern, or a wildcard-only pattern without `confirm: true`"),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 500, description = "Redis failure")
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn clear_cache_handler(
+    State(state): State<Arc<AppState>>,
+    payload: Option<Json<ClearCacheRequest>>,
+) -> Result<Json<ClearCacheResponse>, ApiError> {
+    let Json(payload) = payload.unwrap_or_default();
+    let pattern = match payload.pattern {
+        Some(p) if p.trim().is_empty() => {
+            return Err(ApiError::Validation("pattern must not be empty".to_string()));
+        }
+        Some(p) => p.trim().to_string(),
+        None => DEFAULT_PATTERN.to_string(),
+    };
+
+    // `*`, `**`, ... would also wipe rate-limit counters and synced data
+    if pattern.chars().all(|c| c == '*') && payload.confirm != Some(true) {
+        return Err(ApiError::Validation(format!(
+            "Pattern '{}' matches every key; pass \"confirm\": true to proceed",
+            pattern
+        )));
+    }
+
+    let keys_removed = state.cache.clear_cache(&pattern).await?;
+    tracing::info!("Cleared {} cache keys matching {}", keys_removed, pattern);
+
+    Ok(Json(ClearCacheResponse {
+        success: true,
+        pattern,
+        keys_removed,
+    }))
+}
@@ -2,6 +2,12 @@ use axum::{
     response::Html,
 };
 
+#[utoipa::path(
+    get,
+    path = "/admin/dashboard",
+    tag = "admin",
+    responses((status = 200, description = "Admin dashboard page", content_type = "text/html"))
+)]
 pub async fn dashboard_handler() -> Html<&'static str> {
     Html(include_str!("dashboard.html"))
 }
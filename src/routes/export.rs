@@ -0,0 +1,120 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::IntoParams;
+use crate::AppState;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQuery {
+    /// Folder to export, or `all` to dump every folder.
+    pub folder_name: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct ExportRow {
+    seven_tv_id: String,
+    emote_name: String,
+    file_name: String,
+    url: String,
+    owner_name: Option<String>,
+    animated: bool,
+    tags: Option<Vec<String>>,
+    folder_name: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/export/stickers.csv",
+    tag = "folders",
+    params(ExportQuery),
+    responses((status = 200, description = "Stickers as CSV", content_type = "text/csv"))
+)]
+pub async fn export_stickers_csv_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ExportQuery>,
+) -> Response {
+    let export_all = params.folder_name == "all";
+
+    let rows = if export_all {
+        sqlx::query_as::<_, ExportRow>(
+            "SELECT seven_tv_id, emote_name, file_name, url, owner_name, animated, tags, folder_name FROM stickers ORDER BY folder_name, id"
+        )
+        .fetch_all(&state.db)
+        .await
+    } else {
+        sqlx::query_as::<_, ExportRow>(
+            "SELECT seven_tv_id, emote_name, file_name, url, owner_name, animated, tags, folder_name FROM stickers WHERE folder_name = $1 ORDER BY id"
+        )
+        .bind(&params.folder_name)
+        .fetch_all(&state.db)
+        .await
+    };
+
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!("Failed to export stickers: {:?}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "success": false,
+                    "message": format!("Database error: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let mut csv = String::new();
+    let mut header_fields = vec!["seven_tv_id", "emote_name", "file_name", "url", "owner_name", "animated", "tags"];
+    if export_all {
+        header_fields.push("folder_name");
+    }
+    csv.push_str(&header_fields.join(","));
+    csv.push_str("\r\n");
+
+    for row in rows {
+        let mut fields = vec![
+            row.seven_tv_id,
+            row.emote_name,
+            row.file_name,
+            row.url,
+            row.owner_name.unwrap_or_default(),
+            row.animated.to_string(),
+            row.tags.unwrap_or_default().join("|"),
+        ];
+        if export_all {
+            fields.push(row.folder_name);
+        }
+        let line: Vec<String> = fields.iter().map(|f| escape_csv_field(f)).collect();
+        csv.push_str(&line.join(","));
+        csv.push_str("\r\n");
+    }
+
+    let file_name = format!("stickers_{}.csv", params.folder_name.replace(['/', ':', '"'], "_"));
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)),
+        ],
+        csv,
+    )
+        .into_response()
+}
+
+/// Quotes a field per RFC 4180 when it contains a delimiter, quote or newline.
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,769 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+use crate::models::{ApiError, EmoteResponse, InvalidFieldsResponse};
+use crate::services::error::ServiceError;
+use crate::services::seventv::ProcessOptions;
+use crate::AppState;
+use super::StickerRow;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FoldersQuery {
+    /// Only list folders starting with this prefix (e.g. `trending_sync:`).
+    pub prefix: Option<String>,
+}
+
+#[derive(Serialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSummary {
+    folder_name: String,
+    emote_count: i64,
+    last_added_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct FoldersListResponse {
+    success: bool,
+    folders: Vec<FolderSummary>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/folders",
+    tag = "folders",
+    params(FoldersQuery),
+    responses((status = 200, description = "Folders with their emote counts", body = FoldersListResponse))
+)]
+pub async fn list_folders_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<FoldersQuery>,
+) -> Result<Json<FoldersListResponse>, (StatusCode, Json<FoldersListResponse>)> {
+    let rows = sqlx::query_as::<_, FolderSummary>(
+        r#"
+        SELECT folder_name, COUNT(*) AS emote_count, MAX(created_at) AS last_added_at
+        FROM stickers
+        WHERE $1::TEXT IS NULL OR starts_with(folder_name, $1)
+        GROUP BY folder_name
+        ORDER BY emote_count DESC, folder_name
+        "#
+    )
+    .bind(&params.prefix)
+    .fetch_all(&state.db)
+    .await;
+
+    match rows {
+        Ok(folders) => Ok(Json(FoldersListResponse {
+            success: true,
+            folders,
+        })),
+        Err(e) => {
+            tracing::error!("Failed to list folders: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(FoldersListResponse {
+                    success: false,
+                    folders: vec![],
+                }),
+            ))
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RenameFolderRequest {
+    from: String,
+    to: String,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameFolderResponse {
+    success: bool,
+    from: String,
+    to: String,
+    blobs_moved: usize,
+    stickers_updated: u64,
+    users_updated: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+fn is_trending_folder(folder: &str) -> bool {
+    folder.starts_with("trending/") || folder.starts_with("trending_sync:")
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/folders/rename",
+    request_body = RenameFolderRequest,
+    responses(
+        (status = 200, description = "Folder renamed", body = RenameFolderResponse),
+        (status = 400, description = "Invalid folder names", body = RenameFolderResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 404, description = "Source folder not found", body = RenameFolderResponse),
+        (status = 409, description = "Target folder already exists", body = RenameFolderResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn rename_folder_handler(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<RenameFolderRequest>,
+) -> (StatusCode, Json<RenameFolderResponse>) {
+    let from = payload.from.trim().trim_end_matches('/').to_string();
+    let to = payload.to.trim().trim_end_matches('/').to_string();
+    let mut response = RenameFolderResponse {
+        success: false,
+        from: from.clone(),
+        to: to.clone(),
+        blobs_moved: 0,
+        stickers_updated: 0,
+        users_updated: 0,
+        message: None,
+    };
+
+    if from.is_empty() || to.is_empty() || from == to {
+        response.message = Some("from and to must be different, non-empty folder names".to_string());
+        return (StatusCode::BAD_REQUEST, Json(response));
+    }
+    if is_trending_folder(&from) || is_trending_folder(&to) {
+        response.message = Some("Trending folders are managed by sync-trending and cannot be renamed".to_string());
+        return (StatusCode::BAD_REQUEST, Json(response));
+    }
+    // Existing folders may predate the naming rules, but new names must follow them
+    if let Err(e) = super::validate_folder_name(&to) {
+        response.message = Some(format!("Invalid target folder: {}", e));
+        return (StatusCode::BAD_REQUEST, Json(response));
+    }
+
+    let exists = |folder: String| {
+        let db = state.db.clone();
+        async move {
+            sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM users WHERE folder_name = $1) OR EXISTS(SELECT 1 FROM stickers WHERE folder_name = $1)"
+            )
+            .bind(folder)
+            .fetch_one(&db)
+            .await
+        }
+    };
+
+    match (exists(from.clone()).await, exists(to.clone()).await) {
+        (Ok(false), Ok(_)) => {
+            response.message = Some("Folder not found".to_string());
+            return (StatusCode::NOT_FOUND, Json(response));
+        }
+        (Ok(true), Ok(true)) => {
+            response.message = Some(format!("Folder {} already exists", to));
+            return (StatusCode::CONFLICT, Json(response));
+        }
+        (Ok(true), Ok(false)) => {}
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to look up folders {} / {}: {:?}", from, to, e);
+            response.message = Some(format!("Database error: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    }
+
+    let source_prefix = format!("{}/", from);
+    let target_prefix = format!("{}/", to);
+
+    // Blobs can exist without rows (e.g. a failed sync), so check storage too
+    match state.storage.list_blobs(&target_prefix).await {
+        Ok(existing) if !existing.is_empty() => {
+            response.message = Some(format!("Folder {} already exists in storage", to));
+            return (StatusCode::CONFLICT, Json(response));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Failed to list blobs under {}: {:?}", target_prefix, e);
+            response.message = Some(format!("Failed to list stored emotes: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    }
+
+    let blobs = match state.storage.list_blobs(&source_prefix).await {
+        Ok(blobs) => blobs,
+        Err(e) => {
+            tracing::error!("Failed to list blobs under {}: {:?}", source_prefix, e);
+            response.message = Some(format!("Failed to list stored emotes: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    };
+
+    // Copy everything first; the old folder stays intact until the DB points at the new one
+    for blob in &blobs {
+        let destination = format!("{}{}", target_prefix, &blob[source_prefix.len()..]);
+        if let Err(e) = state.storage.copy_blob(blob, &destination).await {
+            tracing::error!("Failed to copy {} to {}: {:?}", blob, destination, e);
+            discard_copies(&state, &target_prefix).await;
+            response.message = Some(format!("Failed to copy stored emotes: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    }
+
+    let container_url = state.storage.get_container_url();
+    match rename_folder_rows(
+        &state.db,
+        &from,
+        &to,
+        &format!("{}/{}", container_url, source_prefix),
+        &format!("{}/{}", container_url, target_prefix),
+    ).await {
+        Ok((stickers, users)) => {
+            response.stickers_updated = stickers;
+            response.users_updated = users;
+        }
+        Err(e) => {
+            tracing::error!("Failed to rename DB rows {} -> {}: {:?}", from, to, e);
+            discard_copies(&state, &target_prefix).await;
+            response.message = Some(format!("Database error: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    }
+    response.blobs_moved = blobs.len();
+
+    if let Err(e) = state.storage.delete_blobs_by_prefix(&source_prefix).await {
+        tracing::error!("Renamed {} -> {} but failed to delete old blobs: {:?}", from, to, e);
+        response.message = Some(format!("Folder renamed, but some old blobs were left behind: {}", e));
+    }
+
+    for folder in [&from, &to] {
+        let cache_key = format!("user_emotes:{}", folder);
+        if let Err(e) = state.cache.delete_key(&cache_key).await {
+            tracing::error!("Failed to purge cache key {}: {:?}", cache_key, e);
+        }
+    }
+
+    response.success = true;
+    response.message.get_or_insert_with(|| "Folder renamed successfully".to_string());
+    (StatusCode::OK, Json(response))
+}
+
+/// Best-effort cleanup of a half-finished copy so the target name stays free.
+async fn discard_copies(state: &AppState, target_prefix: &str) {
+    if let Err(e) = state.storage.delete_blobs_by_prefix(target_prefix).await {
+        tracing::error!("Failed to clean up copied blobs under {}: {:?}", target_prefix, e);
+    }
+}
+
+/// Moves the `stickers` and `users` rows of `from` to `to` in one transaction,
+/// rewriting sticker URLs that point into the old folder. Returns
+/// `(stickers_updated, users_updated)`.
+async fn rename_folder_rows(
+    db: &sqlx::PgPool,
+    from: &str,
+    to: &str,
+    old_url_prefix: &str,
+    new_url_prefix: &str,
+) -> Result<(u64, u64), sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let stickers = sqlx::query(
+        r#"
+        UPDATE stickers
+        SET folder_name = $2,
+            url = CASE WHEN starts_with(url, $3) THEN $4 || substr(url, length($3) + 1) ELSE url END
+        WHERE folder_name = $1
+        "#
+    )
+    .bind(from)
+    .bind(to)
+    .bind(old_url_prefix)
+    .bind(new_url_prefix)
+    .execute(&mut *tx)
+    .await?;
+    let users = sqlx::query("UPDATE users SET folder_name = $2 WHERE folder_name = $1")
+        .bind(from)
+        .bind(to)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok((stickers.rows_affected(), users.rows_affected()))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ManifestQuery {
+    /// Rebuild the manifest from the folder's `stickers` rows and re-upload it.
+    /// Requires admin credentials.
+    pub regenerate: Option<bool>,
+}
+
+/// Maps a storage folder to the `folder_name` its rows use in `stickers`;
+/// trending syncs store `trending/{period}/{type}` as `trending_sync:{period}:{animated}`.
+fn db_folder_name(folder: &str) -> String {
+    match folder.strip_prefix("trending/").and_then(|rest| rest.split_once('/')) {
+        Some((period, kind)) => format!("trending_sync:{}:{}", period, kind == "animated"),
+        None => folder.to_string(),
+    }
+}
+
+/// Downloads a folder's emotes as a JSON array, for backups or re-importing
+/// elsewhere. Built from the folder's `stickers` rows; trending folders fall
+/// back to the `_metadata.json` their sync wrote when they have no rows. They
+/// contain slashes, so pass them URL-encoded
+/// (e.g. `trending%2Ftrending_weekly%2Fanimated`).
+#[utoipa::path(
+    get,
+    path = "/api/folders/{folder_name}/manifest",
+    tag = "folders",
+    params(("folder_name" = String, Path, description = "Storage folder"), ManifestQuery),
+    responses(
+        (status = 200, description = "The folder's manifest, served as an attachment", body = Vec<EmoteResponse>),
+        (status = 401, description = "`regenerate` without admin credentials", body = SearchResponse),
+        (status = 404, description = "No stickers or manifest for the folder", body = SearchResponse),
+        (status = 500, description = "Manifest is unreadable or storage failed", body = SearchResponse)
+    )
+)]
+pub async fn folder_manifest_handler(
+    State(state): State<Arc<AppState>>,
+    Path(folder_name): Path<String>,
+    Query(params): Query<ManifestQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let blob_name = format!("{}/_metadata.json", folder_name);
+
+    if params.regenerate.unwrap_or(false) {
+        // Reading is public, but rewriting storage is an admin operation
+        if !super::auth::is_admin(&state.config, &headers) {
+            return Err(ApiError::Unauthorized("Missing or invalid admin credentials".to_string()));
+        }
+
+        let manifest = load_folder_stickers(&state, &folder_name).await?;
+        if manifest.is_empty() {
+            return Err(ApiError::NotFound(format!("No stickers saved for folder {}", folder_name)));
+        }
+
+        let data = serde_json::to_vec(&manifest).map_err(ServiceError::from)?;
+        state.storage.upload_blob(data, &blob_name, "application/json", true).await?;
+        tracing::info!("Regenerated manifest for {} with {} emotes", folder_name, manifest.len());
+        return Ok(manifest_attachment(&folder_name, manifest));
+    }
+
+    let manifest = load_folder_stickers(&state, &folder_name).await?;
+    if !manifest.is_empty() {
+        return Ok(manifest_attachment(&folder_name, manifest));
+    }
+    if !is_trending_folder(&folder_name) {
+        return Err(ApiError::NotFound(format!("No stickers saved for folder {}", folder_name)));
+    }
+
+    let data = match state.storage.get_blob_content(&blob_name).await {
+        Ok(data) => data,
+        Err(ServiceError::NotFound) => {
+            return Err(ApiError::NotFound(format!("No manifest for folder {}", folder_name)));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    match serde_json::from_slice::<Vec<EmoteResponse>>(&data) {
+        Ok(manifest) => Ok(manifest_attachment(&folder_name, manifest)),
+        Err(e) => {
+            tracing::error!("Manifest {} is not a valid emote list: {:?}", blob_name, e);
+            Err(ApiError::Storage(format!("Manifest for folder {} is corrupt: {}", folder_name, e)))
+        }
+    }
+}
+
+async fn load_folder_stickers(state: &AppState, folder_name: &str) -> Result<Vec<EmoteResponse>, ApiError> {
+    let rows = sqlx::query_as::<_, StickerRow>(
+        "SELECT seven_tv_id, emote_name, file_name, url, owner_name, tags, animated, scale, mime, width, height, zero_width, size_bytes FROM stickers WHERE folder_name = $1 ORDER BY id"
+    )
+    .bind(db_folder_name(folder_name))
+    .fetch_all(&state.db)
+    .await?;
+    Ok(rows.into_iter().map(EmoteResponse::from).collect())
+}
+
+fn manifest_attachment(folder_name: &str, manifest: Vec<EmoteResponse>) -> Response {
+    let file_stem: String = folder_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    (
+        [(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}-manifest.json\"", file_stem),
+        )],
+        Json(manifest),
+    )
+        .into_response()
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedImportEntry {
+    /// Position of the entry in the submitted array.
+    index: usize,
+    reason: String,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportFolderResponse {
+    success: bool,
+    folder_name: String,
+    /// Entries that weren't in the folder yet.
+    imported: usize,
+    /// Entries that replaced an existing row for the same emote.
+    updated: usize,
+    skipped: Vec<SkippedImportEntry>,
+}
+
+/// Checks one manifest entry, returning why it can't be imported.
+fn parse_import_entry(value: serde_json::Value) -> Result<EmoteResponse, String> {
+    let emote: EmoteResponse = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    let required = [
+        ("emoteId", &emote.emote_id),
+        ("emoteName", &emote.emote_name),
+        ("url", &emote.url),
+        ("fileName", &emote.file_name),
+    ];
+    if let Some((field, _)) = required.iter().find(|(_, value)| value.trim().is_empty()) {
+        return Err(format!("{} must not be empty", field));
+    }
+    Ok(emote)
+}
+
+/// Restores a folder from a manifest (as served by the manifest endpoint)
+/// by writing its `stickers` rows directly. Nothing is downloaded from 7TV,
+/// so the entries' URLs must still point at stored images.
+#[utoipa::path(
+    post,
+    path = "/api/admin/folders/{folder_name}/import",
+    params(("folder_name" = String, Path, description = "Folder to import into")),
+    request_body = Vec<EmoteResponse>,
+    responses(
+        (status = 200, description = "Import summary; malformed entries are listed in `skipped`", body = ImportFolderResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 422, description = "Invalid or reserved folder name", body = InvalidFieldsResponse),
+        (status = 500, description = "Database failure; nothing was imported", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn import_folder_handler(
+    State(state): State<Arc<AppState>>,
+    Path(folder_name): Path<String>,
+    Json(entries): Json<Vec<serde_json::Value>>,
+) -> Result<Json<ImportFolderResponse>, ApiError> {
+    // Also keeps imports out of the trending and global folders syncs manage
+    super::validate_folder_name(&folder_name)?;
+
+    let mut emotes = Vec::with_capacity(entries.len());
+    let mut skipped = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        match parse_import_entry(entry) {
+            Ok(emote) => emotes.push(emote),
+            Err(reason) => skipped.push(SkippedImportEntry { index, reason }),
+        }
+    }
+
+    let mut imported = 0;
+    let mut updated = 0;
+    let mut tx = state.db.begin().await?;
+    for emote in &emotes {
+        if super::upsert_sticker(&mut *tx, &folder_name, emote).await? {
+            imported += 1;
+        } else {
+            updated += 1;
+        }
+    }
+    if imported > 0 {
+        sqlx::query("UPDATE users SET emote_count = COALESCE(emote_count, 0) + $2 WHERE folder_name = $1")
+            .bind(&folder_name)
+            .bind(imported as i32)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    let cache_key = format!("user_emotes:{}", folder_name);
+    if let Err(e) = state.cache.delete_key(&cache_key).await {
+        tracing::error!("Failed to purge cache key {}: {:?}", cache_key, e);
+    }
+
+    tracing::info!(
+        "Imported {} new and {} updated emotes into {} ({} skipped)",
+        imported, updated, folder_name, skipped.len()
+    );
+    Ok(Json(ImportFolderResponse {
+        success: true,
+        folder_name,
+        imported,
+        updated,
+        skipped,
+    }))
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AddFolderEmoteRequest {
+    emote_id: String,
+    preferred_scale: Option<i32>,
+    convert_to_png: Option<bool>,
+}
+
+/// 7TV IDs are 26-character ULIDs, or 24-character hex ObjectIDs for older emotes.
+fn is_valid_emote_id(id: &str) -> bool {
+    matches!(id.len(), 24 | 26) && id.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Fetches one emote from 7TV and stores it into an existing folder without
+/// touching the rest of the folder.
+#[utoipa::path(
+    post,
+    path = "/api/admin/folders/{folder_name}/emotes",
+    params(("folder_name" = String, Path, description = "Folder to add the emote to")),
+    request_body = AddFolderEmoteRequest,
+    responses(
+        (status = 200, description = "The stored emote", body = EmoteResponse),
+        (status = 400, description = "Malformed emote ID", body = SearchResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 422, description = "Invalid or reserved folder name", body = InvalidFieldsResponse),
+        (status = 404, description = "Emote not found on 7TV", body = SearchResponse),
+        (status = 502, description = "7TV or storage failure", body = SearchResponse),
+        (status = 503, description = "Storage not configured", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn add_folder_emote_handler(
+    State(state): State<Arc<AppState>>,
+    Path(folder_name): Path<String>,
+    Json(payload): Json<AddFolderEmoteRequest>,
+) -> Result<Json<EmoteResponse>, ApiError> {
+    let emote_id = payload.emote_id.trim();
+    if !is_valid_emote_id(emote_id) {
+        return Err(ApiError::Validation(format!("'{}' is not a valid 7TV emote ID", emote_id)));
+    }
+    super::validate_folder_name(&folder_name)?;
+    super::ensure_storage(&state)?;
+
+    let emote = match state.seventv.fetch_emote_by_id(emote_id).await? {
+        Some(emote) => emote,
+        None => return Err(ApiError::NotFound(format!("Emote {} not found on 7TV", emote_id))),
+    };
+
+    let options = ProcessOptions {
+        preferred_scale: payload.preferred_scale,
+        preferred_format: None,
+        convert_to_png: payload.convert_to_png.unwrap_or(false),
+        overwrite: true,
+    };
+    let stored = state.seventv.process_emote(emote, &folder_name, options).await
+        .map_err(|reason| ApiError::Upstream(format!("Failed to process emote {}: {}", emote_id, reason)))?;
+
+    let mut tx = state.db.begin().await?;
+    let inserted = super::upsert_sticker(&mut *tx, &folder_name, &stored).await?;
+    if inserted {
+        sqlx::query("UPDATE users SET emote_count = COALESCE(emote_count, 0) + 1 WHERE folder_name = $1")
+            .bind(&folder_name)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    let cache_key = format!("user_emotes:{}", folder_name);
+    if let Err(e) = state.cache.delete_key(&cache_key).await {
+        tracing::error!("Failed to purge cache key {}: {:?}", cache_key, e);
+    }
+
+    tracing::info!("Added emote {} to folder {}", emote_id, folder_name);
+    Ok(Json(stored))
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveFolderEmoteResponse {
+    success: bool,
+    folder_name: String,
+    emote_id: String,
+    file_name: String,
+}
+
+/// Removes one emote (row and blob) from a folder, leaving the rest alone.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/folders/{folder_name}/emotes/{emote_id}",
+    params(
+        ("folder_name" = String, Path, description = "Folder holding the emote"),
+        ("emote_id" = String, Path, description = "7TV emote ID")
+    ),
+    responses(
+        (status = 200, description = "Emote removed", body = RemoveFolderEmoteResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 404, description = "Emote is not in that folder", body = SearchResponse),
+        (status = 500, description = "Storage or database failure; nothing was removed", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn remove_folder_emote_handler(
+    State(state): State<Arc<AppState>>,
+    Path((folder_name, emote_id)): Path<(String, String)>,
+) -> Result<Json<RemoveFolderEmoteResponse>, ApiError> {
+    let mut tx = state.db.begin().await?;
+
+    let file_name = sqlx::query_scalar::<_, String>(
+        "DELETE FROM stickers WHERE folder_name = $1 AND seven_tv_id = $2 RETURNING file_name"
+    )
+    .bind(&folder_name)
+    .bind(&emote_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Emote {} is not in folder {}", emote_id, folder_name)))?;
+
+    sqlx::query("UPDATE users SET emote_count = GREATEST(COALESCE(emote_count, 0) - 1, 0) WHERE folder_name = $1")
+        .bind(&folder_name)
+        .execute(&mut *tx)
+        .await?;
+
+    // Dropping the transaction on failure rolls the row deletion back
+    let blob_name = format!("{}/{}", folder_name, file_name);
+    state.storage.delete_blob(&blob_name).await?;
+    tx.commit().await?;
+
+    let cache_key = format!("user_emotes:{}", folder_name);
+    if let Err(e) = state.cache.delete_key(&cache_key).await {
+        tracing::error!("Failed to purge cache key {}: {:?}", cache_key, e);
+    }
+
+    tracing::info!("Removed emote {} from folder {}", emote_id, folder_name);
+    Ok(Json(RemoveFolderEmoteResponse {
+        success: true,
+        folder_name,
+        emote_id,
+        file_name,
+    }))
+}
+
+// Covers a full 7TV emote set so a large set isn't reported as "added"
+const DEFAULT_DIFF_LIMIT: i32 = 1000;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FolderDiffQuery {
+    /// How many emotes to fetch from the live set (default 1000).
+    pub limit: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffEmote {
+    emote_id: String,
+    emote_name: String,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedEmote {
+    emote_id: String,
+    stored_name: String,
+    live_name: String,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderDiffResponse {
+    success: bool,
+    folder_name: String,
+    seven_tv_id: String,
+    /// True when any of `added`, `removed` or `renamed` is non-empty.
+    stale: bool,
+    /// In the live 7TV set but not stored.
+    added: Vec<DiffEmote>,
+    /// Stored but no longer in the live 7TV set.
+    removed: Vec<DiffEmote>,
+    renamed: Vec<RenamedEmote>,
+}
+
+/// Compares a synced user folder against the user's current 7TV emote set
+/// without touching storage, so callers can decide whether to re-sync.
+#[utoipa::path(
+    get,
+    path = "/api/admin/folders/{folder_name}/diff",
+    params(("folder_name" = String, Path, description = "Synced user folder"), FolderDiffQuery),
+    responses(
+        (status = 200, description = "Differences between the stored folder and 7TV", body = FolderDiffResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 404, description = "Folder isn't a synced user folder", body = SearchResponse),
+        (status = 502, description = "7TV request failed", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn folder_diff_handler(
+    State(state): State<Arc<AppState>>,
+    Path(folder_name): Path<String>,
+    Query(params): Query<FolderDiffQuery>,
+) -> Result<Json<FolderDiffResponse>, ApiError> {
+    let limit = params.limit.unwrap_or(DEFAULT_DIFF_LIMIT).max(1);
+
+    let seven_tv_id = sqlx::query_scalar::<_, String>("SELECT seven_tv_id FROM users WHERE folder_name = $1")
+        .bind(&folder_name)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("No synced user for folder {}", folder_name)))?;
+
+    let stored: Vec<(String, String)> = sqlx::query_as(
+        "SELECT seven_tv_id, emote_name FROM stickers WHERE folder_name = $1 ORDER BY id"
+    )
+    .bind(&folder_name)
+    .fetch_all(&state.db)
+    .await?;
+
+    let live = state.seventv.fetch_user_emotes(&seven_tv_id, limit).await?;
+
+    let stored_names: std::collections::HashMap<&str, &str> = stored
+        .iter()
+        .map(|(id, name)| (id.as_str(), name.as_str()))
+        .collect();
+    let live_ids: std::collections::HashSet<&str> = live.iter().map(|e| e.id.as_str()).collect();
+
+    let mut added = Vec::new();
+    let mut renamed = Vec::new();
+    for emote in &live {
+        // Same name the sync would store
+        let live_name = emote.default_name.as_deref().or(emote.name.as_deref()).unwrap_or_default();
+        match stored_names.get(emote.id.as_str()) {
+            None => added.push(DiffEmote {
+                emote_id: emote.id.clone(),
+                emote_name: live_name.to_string(),
+            }),
+            Some(stored_name) if *stored_name != live_name => renamed.push(RenamedEmote {
+                emote_id: emote.id.clone(),
+                stored_name: stored_name.to_string(),
+                live_name: live_name.to_string(),
+            }),
+            Some(_) => {}
+        }
+    }
+    let removed: Vec<DiffEmote> = stored
+        .iter()
+        .filter(|(id, _)| !live_ids.contains(id.as_str()))
+        .map(|(id, name)| DiffEmote {
+            emote_id: id.clone(),
+            emote_name: name.clone(),
+        })
+        .collect();
+
+    Ok(Json(FolderDiffResponse {
+        success: true,
+        stale: !(added.is_empty() && removed.is_empty() && renamed.is_empty()),
+        folder_name,
+        seven_tv_id,
+        added,
+        removed,
+        renamed,
+    }))
+}
@@ -0,0 +1,161 @@
+use axum::{
+    extract::{Query, State},
+    response::Response,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Instant;
+use utoipa::{IntoParams, ToSchema};
+use crate::models::{ApiError, ImageFormat, InvalidFieldsResponse, SearchResponse};
+use crate::services::seventv::ProcessOptions;
+use crate::AppState;
+use super::jobs;
+
+/// Storage prefix and `stickers.folder_name` for the 7TV global emote set.
+pub const GLOBAL_FOLDER: &str = "global";
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncGlobalRequest {
+    /// Maximum number of global emotes to fetch (default 100).
+    pub limit: Option<i32>,
+    pub preferred_scale: Option<i32>,
+    pub preferred_format: Option<ImageFormat>,
+    pub convert_to_png: Option<bool>,
+    pub callback_url: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalEmotesQuery {
+    pub limit: Option<i32>,
+    pub page: Option<i32>,
+    /// Only return animated emotes.
+    pub animated_only: Option<bool>,
+    /// Only return static emotes.
+    pub static_only: Option<bool>,
+    /// Set to false to leave out zero-width overlay emotes (default true).
+    pub include_zero_width: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/sync-global",
+    params(jobs::SyncModeQuery),
+    request_body = SyncGlobalRequest,
+    responses(
+        (status = 200, description = "Sync finished", body = SearchResponse),
+        (status = 202, description = "Sync queued as a background job"),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 502, description = "7TV request failed", body = SearchResponse),
+        (status = 503, description = "Storage not configured or server shutting down", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn sync_global_handler(
+    State(state): State<Arc<AppState>>,
+    Query(mode): Query<jobs::SyncModeQuery>,
+    payload: Option<Json<SyncGlobalRequest>>,
+) -> Response {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let callback = jobs::SyncCallback {
+        url: payload.callback_url.clone(),
+        folder: GLOBAL_FOLDER.to_string(),
+        period: None,
+    };
+
+    jobs::run_sync(state, mode.run_async.unwrap_or(false), "global", callback, move |state| async move {
+        sync_global(&state, payload).await
+    })
+    .await
+}
+
+async fn sync_global(state: &AppState, payload: SyncGlobalRequest) -> Result<SearchResponse, ApiError> {
+    let start = Instant::now();
+    let (limit, limit_note) = super::checked_limit(state, payload.limit.unwrap_or(100))?;
+    super::validate_preferred_scale(payload.preferred_scale)?;
+    super::ensure_storage(state)?;
+
+    let emotes = state.seventv.fetch_global_emotes(limit).await.map_err(|e| {
+        tracing::error!("Failed to fetch global emotes: {:?}", e);
+        ApiError::from(e)
+    })?;
+
+    // Cleanup existing blobs only once the fetch succeeded
+    super::ensure_running(state)?;
+    if let Err(e) = state.storage.delete_blobs_by_prefix(&format!("{}/", GLOBAL_FOLDER)).await {
+        tracing::error!("Failed to cleanup Azure folder {}: {:?}", GLOBAL_FOLDER, e);
+        return Err(ApiError::Storage(format!("Failed to cleanup existing emotes: {}", e)));
+    }
+
+    let options = ProcessOptions {
+        preferred_scale: payload.preferred_scale,
+        preferred_format: payload.preferred_format,
+        convert_to_png: payload.convert_to_png.unwrap_or(false),
+        overwrite: true,
+    };
+    let batch = state.seventv.process_emotes_batch(emotes, GLOBAL_FOLDER, options).await;
+    let failed_count = batch.failures.len() as i32;
+    let processed = batch.emotes;
+
+    // Emotes dropped from the global set shouldn't linger in listings
+    if let Err(e) = sqlx::query("DELETE FROM stickers WHERE folder_name = $1")
+        .bind(GLOBAL_FOLDER)
+        .execute(&state.db)
+        .await
+    {
+        tracing::error!("Failed to clear global stickers: {:?}", e);
+    }
+    super::upsert_folder_stickers(&state.db, GLOBAL_FOLDER, &processed).await;
+
+    Ok(SearchResponse {
+        success: true,
+        total_found: processed.len() as i32,
+        emotes: processed,
+        message: super::append_note(Some("Global emotes synced successfully".to_string()), limit_note.as_deref()),
+        cached: Some(false),
+        processing_time: Some(super::elapsed_secs(start)),
+        page: Some(1),
+        total_pages: Some(1),
+        results_per_page: Some(limit),
+        has_next_page: Some(false),
+        failed_count: Some(failed_count),
+        animated_count: None,
+        static_count: None,
+        synced_at: None,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/global/emotes",
+    tag = "emotes",
+    params(GlobalEmotesQuery),
+    responses(
+        (status = 200, description = "Stored 7TV global emotes", body = SearchResponse),
+        (status = 404, description = "Global emotes haven't been synced yet", body = SearchResponse),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse)
+    )
+)]
+pub async fn global_emotes_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<GlobalEmotesQuery>,
+) -> Result<Json<SearchResponse>, ApiError> {
+    super::saved_folder_emotes(
+        &state,
+        GLOBAL_FOLDER,
+        params.limit,
+        params.page,
+        None,
+        params.animated_only,
+        params.static_only,
+        params.include_zero_width,
+        None,
+    )
+    .await
+    .map(Json)
+}
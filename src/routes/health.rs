@@ -0,0 +1,118 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use utoipa::ToSchema;
+use crate::AppState;
+
+/// Upper bound for each dependency probe so a hung connection can't stall the check.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    status: &'static str,
+    redis: &'static str,
+    postgres: &'static str,
+    /// Status of the storage backend, whichever it is; named for the
+    /// original Azure-only setup so existing probes keep working.
+    azure: &'static str,
+    /// `azure`, `local`, `s3` or `none`.
+    storage_backend: &'static str,
+}
+
+async fn probe<F, E>(name: &str, check: F) -> &'static str
+where
+    F: Future<Output = Result<(), E>>,
+    E: std::fmt::Display,
+{
+    match tokio::time::timeout(PROBE_TIMEOUT, check).await {
+        Ok(Ok(())) => "up",
+        Ok(Err(e)) => {
+            tracing::warn!("Readiness check: {} is down: {}", name, e);
+            "down"
+        }
+        Err(_) => {
+            tracing::warn!("Readiness check: {} timed out", name);
+            "down"
+        }
+    }
+}
+
+/// Liveness probe: answers as long as the process is serving requests.
+/// Dependencies are deliberately not checked here; see [`ready_handler`].
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "meta",
+    responses((status = 200, description = "Process is alive", body = Object))
+)]
+pub async fn health_handler() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok" }))
+}
+
+/// Readiness probe: 503 until Redis, Postgres and (if configured) storage are reachable.
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "meta",
+    responses(
+        (status = 200, description = "All dependencies reachable", body = ReadinessResponse),
+        (status = 503, description = "A dependency is down", body = ReadinessResponse)
+    )
+)]
+pub async fn ready_handler(
+    State(state): State<Arc<AppState>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let redis = probe("redis", state.cache.ping());
+    let postgres = probe("postgres", async {
+        sqlx::query("SELECT 1").execute(&state.db).await.map(|_| ())
+    });
+    // Storage is optional; without a configured backend it isn't probed at all
+    let azure = async {
+        if state.storage.is_available() {
+            probe(state.storage.backend_name(), state.storage.check_container()).await
+        } else {
+            "disabled"
+        }
+    };
+
+    let (redis, postgres, azure) = tokio::join!(redis, postgres, azure);
+    let healthy = [redis, postgres, azure].iter().all(|s| *s != "down");
+
+    let status_code = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status_code,
+        Json(ReadinessResponse {
+            status: if healthy { "ok" } else { "unavailable" },
+            redis,
+            postgres,
+            azure,
+            storage_backend: state.storage.backend_name(),
+        }),
+    )
+}
+
+/// Prometheus scrape endpoint.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "meta",
+    responses((status = 200, description = "Metrics in the Prometheus text format", content_type = "text/plain"))
+)]
+pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
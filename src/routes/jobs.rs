@@ -0,0 +1,230 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use utoipa::{IntoParams, ToSchema};
+use crate::AppState;
+use crate::models::{ApiError, SearchResponse};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncModeQuery {
+    /// Run the sync in the background and return a job ID immediately.
+    #[serde(rename = "async")]
+    pub run_async: Option<bool>,
+}
+
+/// Where to report a finished sync, if the caller asked for a webhook.
+pub struct SyncCallback {
+    pub url: Option<String>,
+    pub folder: String,
+    pub period: Option<String>,
+}
+
+#[derive(Serialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncJob {
+    id: String,
+    kind: String,
+    folder_name: String,
+    status: String,
+    processed_count: Option<i32>,
+    failed_count: Option<i32>,
+    message: Option<String>,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// `X-Request-Id` of the request that started the job.
+    request_id: Option<String>,
+}
+
+/// Runs a sync either inline (the default) or, when `run_async` is set, as a
+/// background job tracked in `sync_jobs`. The webhook in `callback` fires in
+/// both modes once the sync finishes.
+pub async fn run_sync<F, Fut>(
+    state: Arc<AppState>,
+    run_async: bool,
+    kind: &'static str,
+    callback: SyncCallback,
+    run: F,
+) -> Response
+where
+    F: FnOnce(Arc<AppState>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<SearchResponse, ApiError>> + Send + 'static,
+{
+    if !run_async {
+        let start = Instant::now();
+        let outcome = run(Arc::clone(&state)).await;
+        state.metrics.observe_sync(kind, outcome.is_ok(), start.elapsed().as_secs_f64());
+        notify(&state, callback, &outcome, start);
+        return match outcome {
+            Ok(response) => Json(response).into_response(),
+            Err(e) => e.into_response(),
+        };
+    }
+
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let created = sqlx::query("INSERT INTO sync_jobs (id, kind, folder_name, status, request_id) VALUES ($1, $2, $3, 'pending', $4)")
+        .bind(&job_id)
+        .bind(kind)
+        .bind(&callback.folder)
+        .bind(super::request_id::current())
+        .execute(&state.db)
+        .await;
+
+    if let Err(e) = created {
+        tracing::error!("Failed to create sync job: {:?}", e);
+        return ApiError::from(e).into_response();
+    }
+
+    let task_job_id = job_id.clone();
+    // Outlives the request, so it's counted separately for shutdown draining
+    let in_flight = state.track_in_flight();
+    // Keeps the request ID and span so the job's logs and webhook carry them
+    tokio::spawn(super::request_id::propagate(async move {
+        let _in_flight = in_flight;
+        let start = Instant::now();
+        let _ = sqlx::query("UPDATE sync_jobs SET status = 'running', started_at = NOW() WHERE id = $1")
+            .bind(&task_job_id)
+            .execute(&state.db)
+            .await;
+
+        let outcome = run(Arc::clone(&state)).await;
+        state.metrics.observe_sync(kind, outcome.is_ok(), start.elapsed().as_secs_f64());
+
+        let (status, processed_count, failed_count, message) = match &outcome {
+            Ok(response) => ("completed", response.total_found, response.failed_count.unwrap_or(0), response.message.clone()),
+            Err(e) => ("failed", 0, 0, Some(e.to_string())),
+        };
+        let finished = sqlx::query(
+            r#"
+            UPDATE sync_jobs
+            SET status = $2, processed_count = $3, failed_count = $4, message = $5, finished_at = NOW()
+            WHERE id = $1
+            "#
+        )
+        .bind(&task_job_id)
+        .bind(status)
+        .bind(processed_count)
+        .bind(failed_count)
+        .bind(message)
+        .execute(&state.db)
+        .await;
+
+        if let Err(e) = finished {
+            tracing::error!("Failed to record result of sync job {}: {:?}", task_job_id, e);
+        }
+
+        notify(&state, callback, &outcome, start);
+    }));
+
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "success": true,
+            "jobId": job_id,
+            "status": "pending",
+        })),
+    )
+        .into_response()
+}
+
+fn notify(state: &AppState, callback: SyncCallback, outcome: &Result<SearchResponse, ApiError>, start: Instant) {
+    if let Some(url) = callback.url {
+        super::notify_sync_webhook(state, url, &callback.folder, callback.period, outcome, start);
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/sync-jobs/{id}",
+    params(("id" = String, Path, description = "Job ID returned by an async sync")),
+    responses(
+        (status = 200, description = "Job status", body = SyncJob),
+        (status = 404, description = "Job not found"),
+        (status = 401, description = "Missing or invalid admin credentials")
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn get_sync_job_handler(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<SyncJob>, (StatusCode, Json<serde_json::Value>)> {
+    let job = sqlx::query_as::<_, SyncJob>(
+        "SELECT id, kind, folder_name, status, processed_count, failed_count, message, created_at, started_at, finished_at, request_id FROM sync_jobs WHERE id = $1"
+    )
+    .bind(&job_id)
+    .fetch_optional(&state.db)
+    .await;
+
+    match job {
+        Ok(Some(job)) => Ok(Json(job)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "success": false,
+                "message": format!("Sync job {} not found", job_id),
+            })),
+        )),
+        Err(e) => {
+            tracing::error!("Failed to fetch sync job {}: {:?}", job_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "success": false,
+                    "message": format!("Database error: {}", e),
+                })),
+            ))
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SyncJobsListResponse {
+    success: bool,
+    jobs: Vec<SyncJob>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/sync-jobs",
+    responses(
+        (status = 200, description = "The 100 most recent jobs", body = SyncJobsListResponse),
+        (status = 401, description = "Missing or invalid admin credentials")
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn list_sync_jobs_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<SyncJobsListResponse>, (StatusCode, Json<SyncJobsListResponse>)> {
+    let rows = sqlx::query_as::<_, SyncJob>(
+        "SELECT id, kind, folder_name, status, processed_count, failed_count, message, created_at, started_at, finished_at, request_id FROM sync_jobs ORDER BY created_at DESC LIMIT 100"
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match rows {
+        Ok(jobs) => Ok(Json(SyncJobsListResponse {
+            success: true,
+            jobs,
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch sync jobs: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(SyncJobsListResponse {
+                    success: false,
+                    jobs: vec![],
+                }),
+            ))
+        }
+    }
+}
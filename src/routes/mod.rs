@@ -1,31 +1,249 @@
 use axum::{
-    routing::{get, post},
+    routing::{get, post, delete},
     Router,
     Json,
-    extract::{State, Query},
+    extract::{rejection::QueryRejection, MatchedPath, Request, State, Query, Path},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
 };
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use utoipa::{IntoParams, ToSchema};
 use crate::AppState;
-use crate::models::{TrendingPeriod, SearchResponse, SyncTrendingRequest, EmoteResponse};
+use crate::models::{AnimationFilter, ApiError, FieldError, InvalidFieldsResponse, SearchSort, TrendingPeriod, TrendingPeriodInfo, TrendingPeriodsResponse, SearchResponse, SyncTrendingRequest, SyncWebhookPayload, EmoteResponse, EmoteVariantsResponse};
+use crate::services::seventv::{normalize_tags, preview_emotes, BatchProgress, BatchResult, ProcessOptions, SearchFilters};
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tower_http::compression::{
+    predicate::{NotForContentType, Predicate},
+    CompressionLayer, DefaultPredicate,
+};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::trace::TraceLayer;
+use tracing::Span;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
+mod archive;
+mod auth;
+mod blobs;
+mod bulk_sync;
+mod cache;
 mod dashboard;
+mod export;
+mod folders;
+mod global;
+mod health;
+mod jobs;
+mod openapi;
+mod rate_limit;
+pub(crate) mod request_id;
+mod stats;
+mod stickers;
+mod ws;
 
 pub fn create_router(state: Arc<AppState>) -> Router {
-    Router::new()
+    let admin = Router::new()
+        .route("/api/admin/sync-trending", post(sync_trending_handler))
+        .route("/api/admin/sync-trending/stream", get(sync_trending_stream_handler))
+        .route("/api/admin/sync-user-emotes", post(sync_user_emotes_handler))
+        .route("/api/admin/sync-emote-set", post(sync_emote_set_handler))
+        .route("/api/admin/sync-all-users", post(bulk_sync::sync_all_users_handler))
+        .route("/api/admin/sync-users-batch", post(bulk_sync::sync_users_batch_handler))
+        .route("/api/admin/sync-global", post(global::sync_global_handler))
+        .route("/api/admin/sync-jobs", get(jobs::list_sync_jobs_handler))
+        .route("/api/admin/sync-jobs/:id", get(jobs::get_sync_job_handler))
+        .route("/api/admin/cache/clear", post(cache::clear_cache_handler))
+        .route("/api/admin/stats", get(stats::stats_handler))
+        .route("/api/admin/users", get(list_users_handler))
+        .route("/api/admin/emote-sets", get(list_emote_sets_handler))
+        .route("/api/admin/users/:folder_name", delete(delete_user_folder_handler))
+        .route("/api/admin/users/:folder_name/resync", post(resync_user_handler))
+        .route("/api/admin/user-sync/:folder_name", delete(delete_user_sync_handler))
+        .route("/api/admin/folders/rename", post(folders::rename_folder_handler))
+        .route("/api/admin/folders/:folder_name/emotes", post(folders::add_folder_emote_handler))
+        .route("/api/admin/folders/:folder_name/emotes/:emote_id", delete(folders::remove_folder_emote_handler))
+        .route("/api/admin/folders/:folder_name/diff", get(folders::folder_diff_handler))
+        .route("/api/admin/folders/:folder_name/import", post(folders::import_folder_handler))
+        .route_layer(middleware::from_fn_with_state(Arc::clone(&state), auth::require_admin_key));
+
+    // Endpoints that call out to 7TV on every cache miss are limited per client IP
+    let limited = Router::new()
+        .route("/api/search-emotes", post(search_emotes_handler).get(search_emotes_get_handler))
+        .route("/api/trending/emotes", get(trending_emotes_handler))
+        .route_layer(middleware::from_fn_with_state(Arc::clone(&state), rate_limit::limit_by_ip));
+
+    // Stored emotes are already-compressed images, so they skip compression
+    let blob_routes = Router::new()
+        .route(BLOB_ROUTE, get(blobs::serve_emote_handler));
+
+    let cors = cors_layer(&state.config.cors_allowed_origins);
+    let slow_request = Duration::from_secs(state.config.slow_request_secs);
+
+    let api = Router::new()
         .route("/", get(root_handler))
-        .route("/health", get(health_handler))
+        .route("/health", get(health::health_handler))
+        .route("/metrics", get(health::metrics_handler))
+        .route("/ready", get(health::ready_handler))
+        // The dashboard page is static; the admin calls it makes carry the API key
         .route("/admin/dashboard", get(dashboard::dashboard_handler))
-        .route("/api/search-emotes", post(search_emotes_handler))
-        .route("/api/trending/emotes", get(trending_emotes_handler))
-        .route("/api/admin/sync-trending", post(sync_trending_handler))
+        .route("/api/emote/:id", get(get_emote_handler))
+        .route("/api/emote/:id/variants", get(get_emote_variants_handler))
         .route("/api/trending/synced", get(synced_trending_emotes_handler))
-        .route("/api/admin/sync-user-emotes", post(sync_user_emotes_handler))
+        .route("/api/trending/periods", get(trending_periods_handler))
+        .route("/ws/trending", get(ws::trending_ws_handler))
         .route("/api/user/emotes/saved", get(get_saved_user_emotes_handler))
-        .route("/api/admin/users", get(list_users_handler))
-        .with_state(state)
+        .route("/api/global/emotes", get(global::global_emotes_handler))
+        .route("/api/stickers/search", get(stickers::search_stickers_handler))
+        .route("/api/folders", get(folders::list_folders_handler))
+        .route("/api/folders/:folder_name/archive", get(archive::folder_archive_handler))
+        .route("/api/folders/:folder_name/manifest", get(folders::folder_manifest_handler))
+        .route("/api/export/stickers.csv", get(export::export_stickers_csv_handler))
+        .merge(limited)
+        .merge(admin)
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", openapi::ApiDoc::openapi()));
+
+    let router = compress_except(api, blob_routes)
+        .layer(middleware::from_fn_with_state(Arc::clone(&state), track_in_flight))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(http_span)
+                .on_request(())
+                .on_failure(())
+                .on_response(move |response: &Response, latency: Duration, _: &Span| {
+                    log_response(response.status(), latency, slow_request)
+                }),
+        )
+        .layer(middleware::from_fn(request_id::assign_request_id))
+        .with_state(state);
+
+    match cors {
+        Some(cors) => router.layer(cors),
+        None => router,
+    }
+}
+
+/// Serves stored blobs; see `blobs::serve_emote_handler`.
+const BLOB_ROUTE: &str = "/emotes/:folder/:file_name";
+
+/// Applies `compression_layer` to every route of `api`, then adds the
+/// `uncompressed` routes outside of it.
+fn compress_except<S: Clone + Send + Sync + 'static>(api: Router<S>, uncompressed: Router<S>) -> Router<S> {
+    api.layer(compression_layer()).merge(uncompressed)
+}
+
+/// Gzip or Brotli for JSON and other text responses, following the client's
+/// `Accept-Encoding`. The default predicate already skips images, SSE and
+/// tiny bodies; folder archives are zips and gain nothing either.
+fn compression_layer() -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(DefaultPredicate::new().and(NotForContentType::const_new("application/zip")))
+}
+
+/// Span for one request, nested in the request-ID span (which already has
+/// the method and path). `route` names the matched handler; `body_size` is
+/// the declared upload size of POSTs.
+fn http_span(request: &Request) -> Span {
+    let route = request.extensions().get::<MatchedPath>().map(|p| p.as_str().to_string());
+    let body_size = (request.method() == Method::POST)
+        .then(|| request.headers().get(header::CONTENT_LENGTH))
+        .flatten()
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    tracing::info_span!("http", route = route.as_deref(), body_size)
+}
+
+/// Logs a finished request: warn when slower than `slow_request`, info for
+/// non-2xx and debug otherwise.
+fn log_response(status: StatusCode, latency: Duration, slow_request: Duration) {
+    let latency_ms = latency.as_millis() as u64;
+    if latency > slow_request {
+        tracing::warn!(status = status.as_u16(), latency_ms, "slow request");
+    } else if !status.is_success() {
+        tracing::info!(status = status.as_u16(), latency_ms, "request finished");
+    } else {
+        tracing::debug!(status = status.as_u16(), latency_ms, "request finished");
+    }
+}
+
+/// Counts the request in `AppState::in_flight` so shutdown can wait for it.
+async fn track_in_flight(State(state): State<Arc<AppState>>, request: Request, next: Next) -> Response {
+    let _guard = state.track_in_flight();
+    next.run(request).await
+}
+
+/// Refuses to start a sync once shutdown has begun, so a folder isn't
+/// cleared and then left empty.
+fn ensure_running(state: &AppState) -> Result<(), ApiError> {
+    if state.shutdown.is_cancelled() {
+        return Err(ApiError::Unavailable("Server is shutting down; retry the sync later".to_string()));
+    }
+    Ok(())
 }
 
+/// Rejects work that would write blobs when no storage backend is configured,
+/// before anything is fetched or touched in the database.
+pub(super) fn ensure_storage(state: &AppState) -> Result<(), ApiError> {
+    if !state.storage.is_available() {
+        return Err(ApiError::Unavailable(
+            "Storage not configured: set AZURE_CONNECTION_STRING, S3_BUCKET or STORAGE_BACKEND=local to sync or upload emotes".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Builds the CORS layer from `CORS_ALLOWED_ORIGINS`; `None` when unset so no
+/// CORS headers are sent at all.
+fn cors_layer(allowed_origins: &str) -> Option<CorsLayer> {
+    let origins: Vec<&str> = allowed_origins
+        .split(',')
+        .map(str::trim)
+        .filter(|o| !o.is_empty())
+        .collect();
+
+    if origins.is_empty() {
+        return None;
+    }
+
+    let allow_origin = if origins.contains(&"*") {
+        AllowOrigin::any()
+    } else {
+        let parsed: Vec<HeaderValue> = origins
+            .iter()
+            .filter_map(|origin| match HeaderValue::from_str(origin) {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    tracing::warn!("Ignoring invalid CORS origin: {}", origin);
+                    None
+                }
+            })
+            .collect();
+        AllowOrigin::list(parsed)
+    };
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([header::CONTENT_TYPE, HeaderName::from_static("x-api-key"), request_id::REQUEST_ID_HEADER])
+            .expose_headers([request_id::REQUEST_ID_HEADER]),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "meta",
+    responses((status = 200, description = "API title, description and version", body = Object))
+)]
 async fn root_handler(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "title": state.config.api_title,
@@ -34,335 +252,983 @@ async fn root_handler(State(state): State<Arc<AppState>>) -> Json<serde_json::Va
     }))
 }
 
-async fn health_handler() -> &'static str {
-    "OK"
+/// Upper bound on `queries` in one batch search.
+const MAX_BATCH_QUERIES: usize = 25;
+/// Longest search query, in characters, forwarded to 7TV.
+const MAX_QUERY_LEN: usize = 128;
+/// Batch queries searched at once; each may hit 7TV and storage.
+const BATCH_SEARCH_CONCURRENCY: usize = 4;
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct BatchSearchResponse {
+    /// False if any query failed; failed queries carry `success: false` and a message.
+    success: bool,
+    failed_count: usize,
+    processing_time: f64,
+    results: HashMap<String, SearchResponse>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/search-emotes",
+    tag = "emotes",
+    request_body = SearchRequest,
+    responses(
+        (status = 200, description = "Search results; a `BatchSearchResponse` when `queries` is given", body = SearchResponse),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 429, description = "Rate limit exceeded"),
+        (status = 502, description = "7TV request failed", body = SearchResponse)
+    )
+)]
 async fn search_emotes_handler(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<crate::models::SearchRequest>,
-) -> Json<SearchResponse> {
-    let limit = payload.limit.unwrap_or(20);
+) -> Result<Response, ApiError> {
+    do_search(&state, payload).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/search-emotes",
+    tag = "emotes",
+    params(crate::models::SearchQuery),
+    responses(
+        (status = 200, description = "Search results", body = SearchResponse),
+        (status = 422, description = "Missing or invalid query parameters; invalid fields are listed in `errors`", body = InvalidFieldsResponse),
+        (status = 429, description = "Rate limit exceeded"),
+        (status = 502, description = "7TV request failed", body = SearchResponse)
+    )
+)]
+async fn search_emotes_get_handler(
+    State(state): State<Arc<AppState>>,
+    params: Result<Query<crate::models::SearchQuery>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let Query(params) = params.map_err(|e| ApiError::Unprocessable(e.body_text()))?;
+    do_search(&state, params.into()).await
+}
+
+/// Shared by the POST and GET search routes so both hit the same cache keys.
+async fn do_search(state: &AppState, payload: crate::models::SearchRequest) -> Result<Response, ApiError> {
+    let (limit, limit_note) = clamp_limit(state, payload.limit.unwrap_or(20));
     let page = payload.page.unwrap_or(1);
-    let animated_only = payload.animated_only.unwrap_or(false);
-    
+    let animation = AnimationFilter::resolve(payload.animation, payload.animated_only);
+    let owner = payload.owner.as_deref().map(str::trim).filter(|o| !o.is_empty());
+
+    // Collect every problem so the client can fix them in one go
+    let mut errors = Vec::new();
+    if limit < 1 {
+        errors.push(FieldError::new("limit", format!("must be between 1 and {}", state.config.max_emote_limit)));
+    }
+    if page < 1 {
+        errors.push(FieldError::new("page", "must be at least 1"));
+    }
+    if let Some(scale) = payload.preferred_scale.filter(|s| !(1..=4).contains(s)) {
+        errors.push(FieldError::new("preferredScale", format!("must be between 1 and 4, got {}", scale)));
+    }
+    let sort = match payload.sort_by.as_deref().map(str::trim) {
+        None | Some("") => SearchSort::default(),
+        Some(value) => SearchSort::parse(value).unwrap_or_else(|| {
+            errors.push(FieldError::new("sortBy", format!("unknown value '{}'; expected one of {}", value, SearchSort::ALLOWED)));
+            SearchSort::default()
+        }),
+    };
+    let too_long = |q: &str| q.trim().chars().count() > MAX_QUERY_LEN;
+    match &payload.queries {
+        None if too_long(&payload.query) => {
+            errors.push(FieldError::new("query", format!("must be at most {} characters", MAX_QUERY_LEN)));
+        }
+        Some(queries) if queries.iter().any(|q| too_long(q)) => {
+            errors.push(FieldError::new("queries", format!("each query must be at most {} characters", MAX_QUERY_LEN)));
+        }
+        _ => {}
+    }
+    let tags = normalize_tags(payload.tags);
+    if payload.queries.is_none() && payload.query.trim().is_empty() && tags.is_empty() {
+        errors.push(FieldError::new("query", "must not be empty unless tags are given"));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let options = ProcessOptions {
+        preferred_scale: payload.preferred_scale,
+        preferred_format: payload.preferred_format,
+        ..ProcessOptions::default()
+    };
+    let filters = SearchFilters {
+        tags,
+        tag_match: payload.tag_match.unwrap_or_default(),
+        exact_match: payload.exact_match.unwrap_or(false),
+        case_sensitive: payload.case_sensitive.unwrap_or(true),
+        sort,
+        exclude_zero_width: !payload.include_zero_width.unwrap_or(true),
+    };
+
+    let Some(queries) = payload.queries else {
+        let mut response = search_one(state, &payload.query, limit, page, animation, owner, &filters, options).await?;
+        response.message = append_note(response.message, limit_note.as_deref());
+        return Ok(Json(response).into_response());
+    };
+
+    let mut queries: Vec<String> = queries
+        .into_iter()
+        .map(|q| q.trim().to_string())
+        .filter(|q| !q.is_empty())
+        .collect();
+    queries.sort();
+    queries.dedup();
+
+    if queries.is_empty() {
+        return Err(ApiError::invalid_field("queries", "must contain at least one non-empty query"));
+    }
+    if queries.len() > MAX_BATCH_QUERIES {
+        return Err(ApiError::invalid_field("queries", format!("at most {} queries are allowed per batch", MAX_BATCH_QUERIES)));
+    }
+
+    let start = Instant::now();
+    let results: HashMap<String, SearchResponse> = stream::iter(queries)
+        .map(|query| {
+            let filters = &filters;
+            let limit_note = limit_note.as_deref();
+            async move {
+                let mut response = search_one(state, &query, limit, page, animation, owner, filters, options)
+                    .await
+                    .unwrap_or_else(|e| e.to_search_response());
+                response.message = append_note(response.message, limit_note);
+                (query, response)
+            }
+        })
+        .buffer_unordered(BATCH_SEARCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let failed_count = results.values().filter(|r| !r.success).count();
+    Ok(Json(BatchSearchResponse {
+        success: failed_count == 0,
+        failed_count,
+        processing_time: elapsed_secs(start),
+        results,
+    })
+    .into_response())
+}
+
+/// Caps a client-supplied `limit` at `MAX_EMOTE_LIMIT`. The returned note is
+/// set when the limit was lowered so the response can say why it's short.
+fn clamp_limit(state: &AppState, limit: i32) -> (i32, Option<String>) {
+    let max = state.config.max_emote_limit;
+    if limit > max {
+        (max, Some(format!("limit {} exceeds the maximum and was clamped to {}", limit, max)))
+    } else {
+        (limit, None)
+    }
+}
+
+/// `clamp_limit` for endpoints without their own field validation: a limit
+/// below 1 is rejected with a 422 instead of being passed on.
+fn checked_limit(state: &AppState, limit: i32) -> Result<(i32, Option<String>), ApiError> {
+    if limit < 1 {
+        return Err(ApiError::invalid_field("limit", format!("must be between 1 and {}", state.config.max_emote_limit)));
+    }
+    Ok(clamp_limit(state, limit))
+}
+
+/// Resolves a client-supplied `period`, defaulting to weekly. Unknown values
+/// are rejected so they never end up in a folder name or cache key.
+fn parse_period(period: Option<&str>) -> Result<TrendingPeriod, ApiError> {
+    match period.map(str::trim).filter(|p| !p.is_empty()) {
+        None => Ok(TrendingPeriod::default()),
+        Some(id) => TrendingPeriod::from_id(id).ok_or_else(|| {
+            let allowed = TrendingPeriod::ALL.map(TrendingPeriod::id).join(", ");
+            ApiError::invalid_field("period", format!("unknown value '{}'; expected one of {}", id, allowed))
+        }),
+    }
+}
+
+/// Seconds since `start`, rounded to the millisecond, for `processing_time`.
+fn elapsed_secs(start: Instant) -> f64 {
+    (start.elapsed().as_secs_f64() * 1000.0).round() / 1000.0
+}
+
+/// Appends `note` to a response message.
+fn append_note(message: Option<String>, note: Option<&str>) -> Option<String> {
+    match (message, note) {
+        (Some(message), Some(note)) => Some(format!("{}; {}", message, note)),
+        (None, Some(note)) => Some(note.to_string()),
+        (message, None) => message,
+    }
+}
+
+const MAX_FOLDER_NAME_LEN: usize = 128;
+
+/// Folder names end up in blob prefixes and as `stickers.folder_name`, so keep
+/// them to `[a-zA-Z0-9_-]` and away from the folders syncs manage themselves.
+fn validate_folder_name(name: &str) -> Result<(), ApiError> {
+    if name.is_empty() || name.len() > MAX_FOLDER_NAME_LEN {
+        return Err(ApiError::invalid_field(
+            "folderName",
+            format!("must be 1 to {} characters", MAX_FOLDER_NAME_LEN),
+        ));
+    }
+    if name.starts_with("trending_sync:") || name.starts_with("trending/") || name == global::GLOBAL_FOLDER {
+        return Err(ApiError::invalid_field("folderName", format!("'{}' is reserved", name)));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(ApiError::invalid_field("folderName", "may only contain letters, digits, '_' and '-'"));
+    }
+    Ok(())
+}
+
+/// Rejects image scales 7TV doesn't serve.
+fn validate_preferred_scale(scale: Option<i32>) -> Result<(), ApiError> {
+    match scale {
+        Some(scale) if !(1..=4).contains(&scale) => {
+            Err(ApiError::invalid_field("preferredScale", "must be between 1 and 4"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Searches one query, serving from and populating the response cache.
+#[allow(clippy::too_many_arguments)]
+async fn search_one(
+    state: &AppState,
+    query: &str,
+    limit: i32,
+    page: i32,
+    animation: AnimationFilter,
+    owner: Option<&str>,
+    filters: &SearchFilters,
+    options: ProcessOptions,
+) -> Result<SearchResponse, ApiError> {
+    let start = Instant::now();
+
     // Check cache
-    let cache_key = crate::services::cache::CacheService::get_cache_key(&payload.query, limit, animated_only);
+    let cache_key = crate::services::cache::CacheService::get_cache_key(
+        query,
+        limit,
+        page,
+        animation,
+        owner,
+        options.preferred_format.map(|f| f.as_str()),
+        options.preferred_scale,
+        filters,
+    );
     if let Some(cached_data) = state.cache.get_from_cache(&cache_key).await {
         if let Ok(mut response) = serde_json::from_slice::<SearchResponse>(&cached_data) {
+            state.metrics.record_cache_lookup("search_emotes", true);
             response.cached = Some(true);
-            return Json(response);
+            response.processing_time = Some(elapsed_secs(start));
+            return Ok(response);
         }
     }
+    state.metrics.record_cache_lookup("search_emotes", false);
 
     // Fetch from 7TV
-    let result = state.seventv.search_emotes(&payload.query, page, limit, animated_only).await;
-    match result {
-        Ok(emotes) => {
-            let processed = state.seventv.process_emotes_batch(emotes, "emotes").await;
+    let result = state.seventv.search_and_process(
+        &cache_key, query, page, limit, animation, filters.clone(), "emotes", options
+    ).await;
+    match result.as_ref() {
+        Ok(result) => {
+            let failed_count = result.batch.failures.len() as i32;
+            let mut processed = result.batch.emotes.clone();
+
+            // 7TV search can't filter by owner, so narrow down the page we got back
+            let message = owner.map(|owner| {
+                let wanted = owner.to_lowercase();
+                processed.retain(|e| {
+                    e.owner.as_deref().is_some_and(|o| o.to_lowercase() == wanted)
+                });
+                format!(
+                    "Filtered by owner '{}' within this page of search results; totalFound is the unfiltered count",
+                    owner
+                )
+            });
+
             let response = SearchResponse {
                 success: true,
-                total_found: processed.len() as i32,
+                total_found: result.total_count,
                 emotes: processed,
-                message: None,
+                message,
                 cached: Some(false),
-                processing_time: None,
+                processing_time: Some(elapsed_secs(start)),
                 page: Some(page),
-                total_pages: Some(1), // TODO: fetch from 7TV if needed
+                total_pages: Some(result.page_count),
                 results_per_page: Some(limit),
-                has_next_page: Some(false),
+                has_next_page: Some(page < result.page_count),
+                failed_count: Some(failed_count),
+                animated_count: None,
+                static_count: None,
+                synced_at: None,
             };
             
             // Save to cache
             let _ = state.cache.save_to_cache(&cache_key, &response, state.config.cache_ttl).await;
             
-            Json(response)
+            Ok(response)
         },
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct EmoteQuery {
+    /// Download the emote and store it instead of linking the 7TV CDN.
+    /// Requires `folder`.
+    store: Option<bool>,
+    /// Folder to store into. Follows the usual folder naming rules.
+    folder: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/emote/{id}",
+    tag = "emotes",
+    params(("id" = String, Path, description = "7TV emote ID"), EmoteQuery),
+    responses(
+        (status = 200, description = "The emote, linked on the 7TV CDN or stored when `store=true`", body = EmoteResponse),
+        (status = 404, description = "Emote not found on 7TV", body = SearchResponse),
+        (status = 422, description = "`store=true` without a valid `folder`", body = InvalidFieldsResponse),
+        (status = 502, description = "7TV or storage failure", body = SearchResponse),
+        (status = 503, description = "Storage not configured", body = SearchResponse)
+    )
+)]
+async fn get_emote_handler(
+    State(state): State<Arc<AppState>>,
+    Path(emote_id): Path<String>,
+    Query(params): Query<EmoteQuery>,
+) -> Result<Json<EmoteResponse>, ApiError> {
+    // Storing writes blobs, so check the target before anything is fetched
+    let store_folder = match (params.store.unwrap_or(false), params.folder) {
+        (true, Some(folder)) => {
+            validate_folder_name(&folder)?;
+            ensure_storage(&state)?;
+            Some(folder)
+        }
+        (true, None) => return Err(ApiError::invalid_field("folder", "is required when store=true")),
+        (false, _) => None,
+    };
+    let store = store_folder.is_some();
+
+    // Only plain lookups are cached; a stored emote's URL depends on the folder
+    let cache_key = crate::services::cache::CacheService::get_emote_cache_key(&emote_id);
+    if !store {
+        if let Some(cached_data) = state.cache.get_from_cache(&cache_key).await {
+            if let Ok(emote) = serde_json::from_slice::<EmoteResponse>(&cached_data) {
+                return Ok(Json(emote));
+            }
+        }
+    }
+
+    let emote = match state.seventv.fetch_emote_by_id(&emote_id).await {
+        Ok(Some(emote)) => emote,
+        Ok(None) => {
+            return Err(ApiError::NotFound(format!("Emote {} not found on 7TV", emote_id)));
+        }
         Err(e) => {
-            Json(SearchResponse {
-                success: false,
-                total_found: 0,
-                emotes: vec![],
-                message: Some(e.to_string()),
-                cached: Some(false),
-                processing_time: None,
-                page: None,
-                total_pages: None,
-                results_per_page: None,
-                has_next_page: None,
-            })
+            tracing::error!("Failed to fetch emote {}: {:?}", emote_id, e);
+            return Err(e.into());
+        }
+    };
+
+    if let Some(folder) = store_folder {
+        return state.seventv.process_emote(emote, &folder, ProcessOptions::default()).await
+            .map(Json)
+            .map_err(|reason| ApiError::Upstream(format!("Failed to process emote {}: {}", emote_id, reason)));
+    }
+
+    match crate::services::seventv::describe_emote(emote, ProcessOptions::default(), state.seventv.file_rules()) {
+        Ok(described) => {
+            let _ = state.cache.save_to_cache(&cache_key, &described, state.config.cache_ttl).await;
+            Ok(Json(described))
+        }
+        Err(reason) => Err(ApiError::Upstream(format!("Failed to process emote {}: {}", emote_id, reason))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/emote/{id}/variants",
+    tag = "emotes",
+    params(("id" = String, Path, description = "7TV emote ID")),
+    responses(
+        (status = 200, description = "Every image variant 7TV serves for the emote", body = EmoteVariantsResponse),
+        (status = 404, description = "Emote not found on 7TV", body = SearchResponse),
+        (status = 502, description = "7TV request failed", body = SearchResponse)
+    )
+)]
+async fn get_emote_variants_handler(
+    State(state): State<Arc<AppState>>,
+    Path(emote_id): Path<String>,
+) -> Result<Json<EmoteVariantsResponse>, ApiError> {
+    let cache_key = crate::services::cache::CacheService::get_emote_variants_cache_key(&emote_id);
+    if let Some(cached_data) = state.cache.get_from_cache(&cache_key).await {
+        if let Ok(variants) = serde_json::from_slice::<EmoteVariantsResponse>(&cached_data) {
+            state.metrics.record_cache_lookup("emote_variants", true);
+            return Ok(Json(variants));
+        }
+    }
+    state.metrics.record_cache_lookup("emote_variants", false);
+
+    let emote = match state.seventv.fetch_emote_by_id(&emote_id).await {
+        Ok(Some(emote)) => emote,
+        Ok(None) => {
+            return Err(ApiError::NotFound(format!("Emote {} not found on 7TV", emote_id)));
         }
+        Err(e) => {
+            tracing::error!("Failed to fetch emote {}: {:?}", emote_id, e);
+            return Err(e.into());
+        }
+    };
+
+    match crate::services::seventv::describe_variants(emote, state.seventv.file_rules()) {
+        Ok(variants) => {
+            let _ = state.cache.save_to_cache(&cache_key, &variants, state.config.cache_ttl).await;
+            Ok(Json(variants))
+        }
+        Err(reason) => Err(ApiError::Upstream(format!("Failed to read variants of emote {}: {}", emote_id, reason))),
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct TrendingQuery {
     period: Option<String>,
     limit: Option<i32>,
+    /// 1-based page; only used by `/api/trending/synced`.
+    page: Option<i32>,
+    /// Rows to skip instead of `page`; only used by `/api/trending/synced`.
+    offset: Option<i64>,
+    /// `any` (default), `animated` or `static`.
+    animation: Option<AnimationFilter>,
+    /// Deprecated: use `animation`.
+    #[param(deprecated)]
     animated_only: Option<bool>,
     emote_type: Option<String>,
+    /// Comma-separated 7TV tags (e.g. `pepe,cat`); emotes matching any of them
+    /// are returned. Tags are lowercase words chosen by emote owners. Empty
+    /// means no tag filter.
+    #[serde(default, deserialize_with = "crate::models::comma_separated")]
+    #[param(value_type = Option<String>)]
+    tags: Option<Vec<String>>,
 }
 
+impl TrendingQuery {
+    /// `emote_type=animated` predates `animation` and is still honoured.
+    fn animation(&self) -> AnimationFilter {
+        let animated_only = self.animated_only.unwrap_or(false) || self.emote_type.as_deref() == Some("animated");
+        AnimationFilter::resolve(self.animation, Some(animated_only))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/trending/emotes",
+    tag = "trending",
+    params(TrendingQuery),
+    responses(
+        (status = 200, description = "Trending emotes fetched live from 7TV", body = SearchResponse),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 429, description = "Rate limit exceeded"),
+        (status = 502, description = "7TV request failed", body = SearchResponse)
+    )
+)]
 async fn trending_emotes_handler(
     State(state): State<Arc<AppState>>,
     Query(params): Query<TrendingQuery>,
-) -> Json<SearchResponse> {
-    let limit = params.limit.unwrap_or(20);
-    let animated_only = params.animated_only.unwrap_or(false) || params.emote_type.as_deref() == Some("animated");
-    let period_str = params.period.unwrap_or_else(|| "trending_weekly".to_string());
-    
-    let period = match period_str.as_str() {
-        "trending_daily" => TrendingPeriod::Daily,
-        "trending_monthly" => TrendingPeriod::Monthly,
-        "popularity" => TrendingPeriod::AllTime,
-        _ => TrendingPeriod::Weekly,
-    };
+) -> Result<Json<SearchResponse>, ApiError> {
+    let start = Instant::now();
+    let (limit, limit_note) = checked_limit(&state, params.limit.unwrap_or(20))?;
+    let animation = params.animation();
+    let period = parse_period(params.period.as_deref())?;
+    let tags = normalize_tags(params.tags);
 
     // Construct cache key
     let cache_key = crate::services::cache::CacheService::get_trending_cache_key(
-        &period_str, limit, 1, animated_only
+        period.id(), limit, 1, animation, &tags
     );
 
     if let Some(cached_data) = state.cache.get_from_cache(&cache_key).await {
         if let Ok(mut response) = serde_json::from_slice::<SearchResponse>(&cached_data) {
+            state.metrics.record_cache_lookup("trending_emotes", true);
             response.cached = Some(true);
-            return Json(response);
+            response.processing_time = Some(elapsed_secs(start));
+            response.message = append_note(response.message, limit_note.as_deref());
+            return Ok(Json(response));
         }
     }
+    state.metrics.record_cache_lookup("trending_emotes", false);
 
-    match state.seventv.fetch_trending_emotes(&period, limit, animated_only).await {
-        Ok(emotes) => {
-            let processed = state.seventv.process_emotes_batch(emotes, "trending-emotes").await;
-            let response = SearchResponse {
+    let result = state.seventv.trending_and_process(
+        &cache_key, period, limit, animation, tags, "trending-emotes", ProcessOptions::default()
+    ).await;
+    match result.as_ref() {
+        Ok(batch) => {
+            let failed_count = batch.failures.len() as i32;
+            let processed = batch.emotes.clone();
+            let mut response = SearchResponse {
                 success: true,
                 total_found: processed.len() as i32,
                 emotes: processed,
                 message: None,
                 cached: Some(false),
-                processing_time: None,
+                processing_time: Some(elapsed_secs(start)),
                 page: Some(1),
                 total_pages: Some(1),
                 results_per_page: Some(limit),
                 has_next_page: Some(false),
+                failed_count: Some(failed_count),
+                animated_count: None,
+                static_count: None,
+                synced_at: None,
             };
+            response.count_animation();
 
             let _ = state.cache.save_to_cache(&cache_key, &response, state.config.trending_cache_ttl).await;
-            Json(response)
+            response.message = append_note(response.message, limit_note.as_deref());
+            Ok(Json(response))
         },
         Err(e) => {
             tracing::error!("Failed to fetch trending emotes: {:?}", e);
-            Json(SearchResponse {
-            success: false,
-            total_found: 0,
-            emotes: vec![],
-            message: Some(e.to_string()),
-            cached: Some(false),
-            processing_time: None,
-            page: None,
-            total_pages: None,
-            results_per_page: None,
-            has_next_page: None,
-        })
+            Err(e.into())
+        }
     }
 }
-}
 
+#[utoipa::path(
+    post,
+    path = "/api/admin/sync-trending",
+    params(jobs::SyncModeQuery),
+    request_body = SyncTrendingRequest,
+    responses(
+        (status = 200, description = "Sync finished", body = SearchResponse),
+        (status = 202, description = "Sync queued as a background job"),
+        (status = 400, description = "Invalid request", body = SearchResponse),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 404, description = "User or emote set not found on 7TV", body = SearchResponse),
+        (status = 502, description = "7TV request failed", body = SearchResponse),
+        (status = 503, description = "Storage not configured or server shutting down", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
 async fn sync_trending_handler(
     State(state): State<Arc<AppState>>,
+    Query(mode): Query<jobs::SyncModeQuery>,
     Json(payload): Json<SyncTrendingRequest>,
-) -> Json<SearchResponse> {
+) -> Response {
     let animated_only = payload.animated_only.unwrap_or(false);
-    let period_str = payload.period.unwrap_or_else(|| "trending_weekly".to_string());
-    
-    let period = match period_str.as_str() {
-        "trending_daily" => TrendingPeriod::Daily,
-        "trending_monthly" => TrendingPeriod::Monthly,
-        "popularity" => TrendingPeriod::AllTime,
-        _ => TrendingPeriod::Weekly,
+    let period = match parse_period(payload.period.as_deref()) {
+        Ok(period) => period,
+        Err(e) => return e.into_response(),
+    };
+    let callback = jobs::SyncCallback {
+        url: payload.callback_url.clone(),
+        folder: trending_folder(period, animated_only),
+        period: Some(period.id().to_string()),
     };
 
+    jobs::run_sync(state, mode.run_async.unwrap_or(false), "trending", callback, move |state| async move {
+        sync_trending(&state, payload).await
+    })
+    .await
+}
+
+async fn sync_trending(state: &AppState, payload: SyncTrendingRequest) -> Result<SearchResponse, ApiError> {
+    let start = Instant::now();
+    let animated_only = payload.animated_only.unwrap_or(false);
+    let period = parse_period(payload.period.as_deref())?;
+
     // Use limit from payload if provided, otherwise default to 100
-    let limit = payload.limit.unwrap_or(100);
+    let (limit, limit_note) = checked_limit(state, payload.limit.unwrap_or(100))?;
+    validate_preferred_scale(payload.preferred_scale)?;
 
-    // Define dynamic folder path: trending/{period}/{type}/
+    let options = ProcessOptions {
+        preferred_scale: payload.preferred_scale,
+        preferred_format: payload.preferred_format,
+        convert_to_png: payload.convert_to_png.unwrap_or(false),
+        overwrite: true,
+    };
+
+    let dry_run = payload.dry_run.unwrap_or(false);
+    let tags = normalize_tags(payload.tags);
+
+    let batch = run_trending_sync(state, period, animated_only, limit, &tags, options, dry_run, None).await?;
+    let failed_count = batch.failures.len() as i32;
+    let processed = batch.emotes;
+    let message = if dry_run { DRY_RUN_MESSAGE } else { "Synced successfully" };
+
+    let mut response = SearchResponse {
+        success: true,
+        total_found: processed.len() as i32,
+        emotes: processed,
+        message: append_note(Some(message.to_string()), limit_note.as_deref()),
+        cached: Some(false),
+        processing_time: Some(elapsed_secs(start)),
+        page: Some(1),
+        total_pages: Some(1),
+        results_per_page: Some(limit),
+        has_next_page: Some(false),
+        failed_count: Some(failed_count),
+        animated_count: None,
+        static_count: None,
+        synced_at: None,
+    };
+    response.count_animation();
+    Ok(response)
+}
+
+/// Delivers a sync outcome to `url` in the background.
+fn notify_sync_webhook(
+    state: &AppState,
+    url: String,
+    folder: &str,
+    period: Option<String>,
+    outcome: &Result<SearchResponse, ApiError>,
+    start: Instant,
+) {
+    let summary = SyncWebhookPayload {
+        folder: folder.to_string(),
+        period,
+        success: outcome.is_ok(),
+        processed_count: outcome.as_ref().map_or(0, |r| r.total_found),
+        failed_count: outcome.as_ref().map_or(0, |r| r.failed_count.unwrap_or(0)),
+        duration_secs: start.elapsed().as_secs_f64(),
+        error: outcome.as_ref().err().map(ToString::to_string),
+        request_id: request_id::current(),
+    };
+
+    // Don't hold up the sync response; delivery errors are only logged
+    let seventv = Arc::clone(&state.seventv);
+    tokio::spawn(request_id::propagate(async move {
+        if let Err(e) = seventv.post_webhook(&url, &summary).await {
+            tracing::error!("Failed to deliver sync webhook to {}: {:?}", url, e);
+        }
+    }));
+}
+
+const DRY_RUN_MESSAGE: &str = "Dry run: nothing was deleted, downloaded or saved";
+
+/// Storage folder of a trending sync: `trending/{period}/{animated|static}`.
+fn trending_folder(period: TrendingPeriod, animated_only: bool) -> String {
     let type_str = if animated_only { "animated" } else { "static" };
-    let folder = format!("trending/{}/{}", period_str, type_str);
+    format!("trending/{}/{}", period.id(), type_str)
+}
+
+/// Runs a full trending sync: wipes the trending folder, fetches the period
+/// from 7TV, stores every emote and persists the result to Redis, the Azure
+/// manifest and Postgres. Per-emote progress is reported on `progress`.
+/// With `dry_run` only the fetch happens and a preview batch is returned.
+#[allow(clippy::too_many_arguments)]
+async fn run_trending_sync(
+    state: &AppState,
+    period: TrendingPeriod,
+    animated_only: bool,
+    limit: i32,
+    tags: &[String],
+    options: ProcessOptions,
+    dry_run: bool,
+    progress: Option<mpsc::Sender<BatchProgress>>,
+) -> Result<BatchResult, ApiError> {
+    let folder = trending_folder(period, animated_only);
+    // Synced folders are split by type, so "any" never applies here
+    let animation = if animated_only { AnimationFilter::Animated } else { AnimationFilter::Static };
+
+    if !dry_run {
+        ensure_storage(state)?;
+    }
+    if dry_run {
+        let emotes = state.seventv.fetch_trending_emotes(&period, limit, animation, tags).await?;
+        return Ok(preview_emotes(emotes, options, state.seventv.file_rules()));
+    }
+
+    ensure_running(state)?;
 
     // 1. Cleanup existing blobs in that folder
     if let Err(e) = state.storage.delete_blobs_by_prefix(&format!("{}/", folder)).await {
         tracing::error!("Failed to cleanup Azure folder {}: {:?}", folder, e);
-        // We continue anyway, or maybe return error? 
-        // Let's return error to be safe as per user request of "not mixing"
-        return Json(SearchResponse {
-            success: false,
-            total_found: 0,
-            emotes: vec![],
-            message: Some(format!("Failed to cleanup existing emotes: {}", e)),
-            cached: Some(false),
-            processing_time: None,
-            page: None,
-            total_pages: None,
-            results_per_page: None,
-            has_next_page: None,
-        });
+        // Return an error rather than mixing old and new emotes in one folder
+        return Err(ApiError::Storage(format!("Failed to cleanup existing emotes: {}", e)));
     }
 
-    match state.seventv.fetch_trending_emotes(&period, limit, animated_only).await {
-        Ok(emotes) => {
-            let processed = state.seventv.process_emotes_batch(emotes, &folder).await;
-            
-            // Save to Redis with a special sync key and long TTL (e.g. 24 hours)
-            let sync_key = crate::services::cache::CacheService::get_trending_sync_key(&period_str, animated_only);
-            // 24 hours = 86400 seconds
-            let ttl = 86400; 
-            
-            if let Err(e) = state.cache.save_to_cache(&sync_key, &processed, ttl).await {
-                tracing::error!("Failed to save synced trending emotes to cache: {:?}", e);
-            }
+    let emotes = match state.seventv.fetch_trending_emotes(&period, limit, animation, tags).await {
+        Ok(emotes) => emotes,
+        Err(e) => {
+            tracing::error!("Failed to sync trending emotes: {:?}", e);
+            return Err(e.into());
+        }
+    };
 
-            // Save metadata manifest to Azure
-            let metadata_blob_name = format!("{}/_metadata.json", folder);
-            if let Ok(json_data) = serde_json::to_vec(&processed) {
-                if let Err(e) = state.storage.upload_blob(json_data, &metadata_blob_name, "application/json").await {
-                    tracing::error!("Failed to save metadata to Azure: {:?}", e);
-                }
-            }
+    let batch = state.seventv.process_emotes_batch_with_progress(emotes, &folder, options, progress).await;
+    let processed = &batch.emotes;
 
-            // Save trending stickers to database with a special folder name
-            let db_folder = format!("trending_sync:{}:{}", period_str, animated_only);
-            
-            // First, clear existing stickers for this trending category in DB
-            let _ = sqlx::query("DELETE FROM stickers WHERE folder_name = $1")
-                .bind(&db_folder)
-                .execute(&state.db)
-                .await;
-
-            for emote in &processed {
-                let _ = sqlx::query(
-                    r#"
-                    INSERT INTO stickers (seven_tv_id, emote_name, file_name, url, owner_name, tags, animated, folder_name)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                    "#
-                )
-                .bind(&emote.emote_id)
-                .bind(&emote.emote_name)
-                .bind(&emote.file_name)
-                .bind(&emote.url)
-                .bind(&emote.owner)
-                .bind(&emote.tags)
-                .bind(emote.animated.unwrap_or(false))
-                .bind(&db_folder)
-                .execute(&state.db)
-                .await;
-            }
+    // Save to Redis with a special sync key and long TTL (e.g. 24 hours)
+    let sync_key = crate::services::cache::CacheService::get_trending_sync_key(period.id(), animated_only);
+    // 24 hours = 86400 seconds
+    let ttl = 86400; 
 
-            Json(SearchResponse {
-                success: true,
-                total_found: processed.len() as i32,
-                emotes: processed,
-                message: Some("Synced successfully".to_string()),
-                cached: Some(false),
-                processing_time: None,
-                page: Some(1),
-                total_pages: Some(1),
-                results_per_page: Some(limit),
-                has_next_page: Some(false),
-            })
-        },
-        Err(e) => {
-            tracing::error!("Failed to sync trending emotes: {:?}", e);
-            Json(SearchResponse {
-                success: false,
-                total_found: 0,
-                emotes: vec![],
-                message: Some(e.to_string()),
-                cached: Some(false),
-                processing_time: None,
-                page: None,
-                total_pages: None,
-                results_per_page: None,
-                has_next_page: None,
-            })
+    if let Err(e) = state.cache.save_to_cache(&sync_key, processed, ttl).await {
+        tracing::error!("Failed to save synced trending emotes to cache: {:?}", e);
+    }
+
+    // Save metadata manifest to Azure
+    let metadata_blob_name = format!("{}/_metadata.json", folder);
+    if let Ok(json_data) = serde_json::to_vec(processed) {
+        if let Err(e) = state.storage.upload_blob(json_data, &metadata_blob_name, "application/json", true).await {
+            tracing::error!("Failed to save metadata to Azure: {:?}", e);
         }
     }
+
+    // Save trending stickers to database with a special folder name
+    let db_folder = format!("trending_sync:{}:{}", period.id(), animated_only);
+
+    let synced_at = chrono::Utc::now();
+
+    // First, clear existing stickers for this trending category in DB
+    let _ = sqlx::query("DELETE FROM stickers WHERE folder_name = $1")
+        .bind(&db_folder)
+        .execute(&state.db)
+        .await;
+
+    for emote in processed {
+        let _ = sqlx::query(
+            r#"
+            INSERT INTO stickers (seven_tv_id, emote_name, file_name, url, owner_name, tags, animated, folder_name, scale, mime, width, height, zero_width, synced_at, size_bytes)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            "#
+        )
+        .bind(&emote.emote_id)
+        .bind(&emote.emote_name)
+        .bind(&emote.file_name)
+        .bind(&emote.url)
+        .bind(&emote.owner)
+        .bind(&emote.tags)
+        .bind(emote.animated.unwrap_or(false))
+        .bind(&db_folder)
+        .bind(emote.scale)
+        .bind(&emote.mime)
+        .bind(emote.width)
+        .bind(emote.height)
+        .bind(emote.zero_width.unwrap_or(false))
+        .bind(synced_at)
+        .bind(emote.size_bytes)
+        .execute(&state.db)
+        .await;
+    }
+
+    publish_trending_update(state, &folder, processed);
+
+    Ok(batch)
+}
+
+/// Sends a finished sync to `/ws/trending` subscribers. Having no subscribers is fine.
+fn publish_trending_update(state: &AppState, folder: &str, emotes: &[EmoteResponse]) {
+    let update = SearchResponse {
+        success: true,
+        total_found: emotes.len() as i32,
+        emotes: emotes.to_vec(),
+        message: Some(format!("Trending sync finished for {}", folder)),
+        cached: Some(false),
+        processing_time: None,
+        page: Some(1),
+        total_pages: Some(1),
+        results_per_page: Some(emotes.len() as i32),
+        has_next_page: Some(false),
+        failed_count: None,
+        animated_count: None,
+        static_count: None,
+        synced_at: None,
+    };
+
+    match serde_json::to_string(&update) {
+        Ok(json) => {
+            let _ = state.trending_updates.send(Arc::new(json));
+        }
+        Err(e) => tracing::error!("Failed to serialize trending update: {:?}", e),
+    }
+}
+
+/// Same as `sync_trending_handler`, but streams a `progress` event per
+/// processed emote followed by a final `done` event.
+#[utoipa::path(
+    get,
+    path = "/api/admin/sync-trending/stream",
+    params(SyncTrendingRequest),
+    responses(
+        (status = 200, description = "`progress` events followed by a `done` event", content_type = "text/event-stream"),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 401, description = "Missing or invalid admin credentials")
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+async fn sync_trending_stream_handler(
+    State(state): State<Arc<AppState>>,
+    Query(payload): Query<SyncTrendingRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let start = Instant::now();
+    let animated_only = payload.animated_only.unwrap_or(false);
+    let period = parse_period(payload.period.as_deref())?;
+    // Progress events carry no message, so the stream clamps silently
+    let (limit, _) = checked_limit(&state, payload.limit.unwrap_or(100))?;
+    validate_preferred_scale(payload.preferred_scale)?;
+    let options = ProcessOptions {
+        preferred_scale: payload.preferred_scale,
+        preferred_format: payload.preferred_format,
+        convert_to_png: payload.convert_to_png.unwrap_or(false),
+        overwrite: true,
+    };
+    let dry_run = payload.dry_run.unwrap_or(false);
+    let tags = normalize_tags(payload.tags);
+
+    let (tx, rx) = mpsc::channel(64);
+    // The client may disconnect mid-sync; keep the drain waiting on the task
+    let in_flight = state.track_in_flight();
+    let task = tokio::spawn(request_id::propagate(async move {
+        let _in_flight = in_flight;
+        run_trending_sync(&state, period, animated_only, limit, &tags, options, dry_run, Some(tx)).await
+    }));
+
+    let progress = stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|p| (Event::default().event("progress").json_data(p), rx))
+    });
+
+    let done = stream::once(async move {
+        let summary = match task.await {
+            Ok(Ok(batch)) => serde_json::json!({
+                "success": true,
+                "totalFound": batch.emotes.len(),
+                "failedCount": batch.failures.len(),
+                "processingTime": elapsed_secs(start),
+            }),
+            Ok(Err(e)) => serde_json::json!({
+                "success": false,
+                "message": e.to_string(),
+            }),
+            Err(e) => serde_json::json!({
+                "success": false,
+                "message": format!("Sync task failed: {}", e),
+            }),
+        };
+        Event::default().event("done").json_data(summary)
+    });
+
+    Ok(Sse::new(progress.chain(done)).keep_alive(KeepAlive::default()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/trending/periods",
+    tag = "trending",
+    responses((status = 200, description = "Accepted `period` values", body = TrendingPeriodsResponse))
+)]
+async fn trending_periods_handler() -> Json<TrendingPeriodsResponse> {
+    let periods = TrendingPeriod::ALL
+        .into_iter()
+        .map(|p| TrendingPeriodInfo {
+            id: p.id(),
+            label: p.label(),
+            sort_by: p.sort_by(),
+            default: p == TrendingPeriod::default(),
+        })
+        .collect();
+    Json(TrendingPeriodsResponse { success: true, periods })
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/trending/synced",
+    tag = "trending",
+    params(TrendingQuery),
+    responses(
+        (status = 200, description = "Trending emotes from the last sync", body = SearchResponse),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 404, description = "Nothing has been synced for this period yet", body = SearchResponse)
+    )
+)]
 async fn synced_trending_emotes_handler(
     State(state): State<Arc<AppState>>,
     Query(params): Query<TrendingQuery>,
-) -> Json<SearchResponse> {
-    let limit = params.limit.unwrap_or(20) as i64;
-    let animated_only = params.animated_only.unwrap_or(false) || params.emote_type.as_deref() == Some("animated");
-    let period_str = params.period.unwrap_or_else(|| "trending_weekly".to_string());
+) -> Result<Json<SearchResponse>, ApiError> {
+    let start = Instant::now();
+    let (limit, limit_note) = checked_limit(&state, params.limit.unwrap_or(20))?;
+    let limit = limit as i64;
+    let (page, offset) = page_window(limit, params.page, params.offset)?;
+    // Synced folders only exist per type; "any" reads the static one as before
+    let animated_only = params.animation() == AnimationFilter::Animated;
+    let period = parse_period(params.period.as_deref())?;
 
-    let db_folder = format!("trending_sync:{}:{}", period_str, animated_only);
+    let db_folder = format!("trending_sync:{}:{}", period.id(), animated_only);
 
-    // Query stickers from database
-    let rows = sqlx::query_as::<_, StickerRow>(
-        "SELECT seven_tv_id, emote_name, file_name, url, owner_name, tags, animated FROM stickers WHERE folder_name = $1 LIMIT $2"
+    let summary = sqlx::query_as::<_, (i64, Option<chrono::DateTime<chrono::Utc>>)>(
+        "SELECT COUNT(*), MAX(synced_at) FROM stickers WHERE folder_name = $1"
     )
     .bind(&db_folder)
-    .bind(limit)
-    .fetch_all(&state.db)
+    .fetch_one(&state.db)
     .await;
 
+    // Query stickers from database
+    let rows = match summary {
+        Ok((total, synced_at)) => sqlx::query_as::<_, StickerRow>(
+            "SELECT seven_tv_id, emote_name, file_name, url, owner_name, tags, animated, scale, mime, width, height, zero_width, size_bytes FROM stickers WHERE folder_name = $1 ORDER BY id LIMIT $2 OFFSET $3"
+        )
+        .bind(&db_folder)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&state.db)
+        .await
+        .map(|stickers| (total, synced_at, stickers)),
+        Err(e) => Err(e),
+    };
+
     match rows {
-        Ok(stickers) if !stickers.is_empty() => {
-            let emotes: Vec<EmoteResponse> = stickers.into_iter().map(|s| EmoteResponse {
-                emote_id: s.seven_tv_id,
-                emote_name: s.emote_name,
-                file_name: s.file_name,
-                url: s.url,
-                owner: s.owner_name,
-                tags: s.tags,
-                animated: Some(s.animated),
-                scale: None,
-                mime: None,
-            }).collect();
-
-            Json(SearchResponse {
+        Ok((total, synced_at, stickers)) if total > 0 => {
+            let emotes: Vec<EmoteResponse> = stickers.into_iter().map(EmoteResponse::from).collect();
+
+            let total_pages = (total + limit - 1) / limit;
+
+            Ok(Json(SearchResponse {
                 success: true,
-                total_found: emotes.len() as i32,
+                total_found: total as i32,
                 emotes,
-                message: None,
+                message: limit_note,
                 cached: Some(false),
-                processing_time: None,
-                page: Some(1),
-                total_pages: Some(1),
+                processing_time: Some(elapsed_secs(start)),
+                page: Some(page as i32),
+                total_pages: Some(total_pages as i32),
                 results_per_page: Some(limit as i32),
-                has_next_page: Some(false),
-            })
+                has_next_page: Some(offset + limit < total),
+                failed_count: None,
+                animated_count: None,
+                static_count: None,
+                synced_at,
+            }))
         },
         _ => {
             // Fallback to Redis sync key logic if DB is empty
-            let sync_key = crate::services::cache::CacheService::get_trending_sync_key(&period_str, animated_only);
+            let sync_key = crate::services::cache::CacheService::get_trending_sync_key(period.id(), animated_only);
             if let Some(cached_data) = state.cache.get_from_cache(&sync_key).await {
                 if let Ok(all_emotes) = serde_json::from_slice::<Vec<EmoteResponse>>(&cached_data) {
-                    return return_paginated_response(all_emotes, limit as usize);
+                    let Json(mut response) = return_paginated_response(all_emotes, page as usize, limit as usize, start);
+                    response.message = limit_note;
+                    return Ok(Json(response));
                 }
             }
 
-            Json(SearchResponse {
-                success: false,
-                total_found: 0,
-                emotes: vec![],
-                message: Some("No synced data found in DB or Cache. Please run admin sync.".to_string()),
-                cached: Some(false),
-                processing_time: None,
-                page: None,
-                total_pages: None,
-                results_per_page: None,
-                has_next_page: None,
-            })
+            Err(ApiError::NotFound("No synced data found in DB or Cache. Please run admin sync.".to_string()))
         }
     }
 }
 
-fn return_paginated_response(all_emotes: Vec<EmoteResponse>, limit: usize) -> Json<SearchResponse> {
+fn return_paginated_response(all_emotes: Vec<EmoteResponse>, page: usize, limit: usize, start: Instant) -> Json<SearchResponse> {
     let total = all_emotes.len();
-    let start_index = 0; 
+    let total_pages = total.div_ceil(limit);
+    let start_index = (page - 1) * limit;
     let end_index = std::cmp::min(start_index + limit, total);
-    
+
+    // Pages past the end are empty rather than repeating the first page
     let slice = if start_index < total {
         all_emotes[start_index..end_index].to_vec()
     } else {
@@ -371,45 +1237,202 @@ fn return_paginated_response(all_emotes: Vec<EmoteResponse>, limit: usize) -> Js
 
     Json(SearchResponse {
         success: true,
-        total_found: slice.len() as i32,
+        total_found: total as i32,
         emotes: slice,
         message: None,
         cached: Some(true),
-        processing_time: None,
-        page: Some(1),
-        total_pages: Some(1),
+        processing_time: Some(elapsed_secs(start)),
+        page: Some(page as i32),
+        total_pages: Some(total_pages as i32),
         results_per_page: Some(limit as i32),
-        has_next_page: Some(false),
+        has_next_page: Some(page < total_pages),
+        failed_count: None,
+        animated_count: None,
+        static_count: None,
+        synced_at: None,
     })
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/admin/sync-user-emotes",
+    params(jobs::SyncModeQuery),
+    request_body = crate::models::SyncUserEmotesRequest,
+    responses(
+        (status = 200, description = "Sync finished", body = SearchResponse),
+        (status = 202, description = "Sync queued as a background job"),
+        (status = 400, description = "Invalid request", body = SearchResponse),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 404, description = "User or emote set not found on 7TV", body = SearchResponse),
+        (status = 502, description = "7TV request failed", body = SearchResponse),
+        (status = 503, description = "Storage not configured or server shutting down", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
 async fn sync_user_emotes_handler(
     State(state): State<Arc<AppState>>,
+    Query(mode): Query<jobs::SyncModeQuery>,
     Json(payload): Json<crate::models::SyncUserEmotesRequest>,
-) -> Json<SearchResponse> {
-    let limit = payload.limit.unwrap_or(100);
+) -> Response {
+    if let Err(e) = validate_folder_name(&payload.folder_name) {
+        return e.into_response();
+    }
+
+    let callback = jobs::SyncCallback {
+        url: payload.callback_url.clone(),
+        folder: payload.folder_name.clone(),
+        period: None,
+    };
+
+    jobs::run_sync(state, mode.run_async.unwrap_or(false), "user", callback, move |state| async move {
+        sync_user_emotes(&state, payload).await
+    })
+    .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{folder_name}/resync",
+    params(("folder_name" = String, Path, description = "Folder the user was synced into"), jobs::SyncModeQuery),
+    request_body(content = crate::models::ResyncUserRequest, description = "Optional sync options"),
+    responses(
+        (status = 200, description = "Sync finished", body = SearchResponse),
+        (status = 202, description = "Sync queued as a background job"),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 404, description = "Folder isn't a synced user folder", body = SearchResponse),
+        (status = 502, description = "7TV request failed", body = SearchResponse),
+        (status = 503, description = "Storage not configured or server shutting down", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+async fn resync_user_handler(
+    State(state): State<Arc<AppState>>,
+    Path(folder_name): Path<String>,
+    Query(mode): Query<jobs::SyncModeQuery>,
+    payload: Option<Json<crate::models::ResyncUserRequest>>,
+) -> Response {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+
+    let seven_tv_id = sqlx::query_scalar::<_, String>("SELECT seven_tv_id FROM users WHERE folder_name = $1")
+        .bind(&folder_name)
+        .fetch_optional(&state.db)
+        .await;
+    let seven_tv_id = match seven_tv_id {
+        Ok(Some(id)) => id,
+        Ok(None) => {
+            return ApiError::NotFound(format!("No synced user for folder {}", folder_name)).into_response();
+        }
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+
+    let request = crate::models::SyncUserEmotesRequest {
+        user_id: Some(seven_tv_id),
+        platform_username: None,
+        platform: None,
+        limit: payload.limit,
+        folder_name,
+        preferred_scale: payload.preferred_scale,
+        preferred_format: payload.preferred_format,
+        callback_url: payload.callback_url,
+        convert_to_png: payload.convert_to_png,
+        dry_run: payload.dry_run,
+    };
+    let callback = jobs::SyncCallback {
+        url: request.callback_url.clone(),
+        folder: request.folder_name.clone(),
+        period: None,
+    };
+
+    jobs::run_sync(state, mode.run_async.unwrap_or(false), "user", callback, move |state| async move {
+        sync_user_emotes(&state, request).await
+    })
+    .await
+}
+
+/// Runs a user sync (resolve user, wipe folder, fetch active set, store and
+/// persist) and returns the response body describing the outcome.
+async fn sync_user_emotes(
+    state: &AppState,
+    payload: crate::models::SyncUserEmotesRequest,
+) -> Result<SearchResponse, ApiError> {
+    let start = Instant::now();
+    let (limit, limit_note) = checked_limit(state, payload.limit.unwrap_or(100))?;
     let folder = payload.folder_name;
+    validate_preferred_scale(payload.preferred_scale)?;
+    if !payload.dry_run.unwrap_or(false) {
+        ensure_storage(state)?;
+    }
+
+    // Resolve the 7TV user ID up front so a bad username never wipes the folder
+    let user_id = match (payload.user_id, payload.platform_username) {
+        (Some(user_id), _) => user_id,
+        (None, Some(username)) => {
+            let platform = payload.platform.unwrap_or_else(|| "TWITCH".to_string());
+            match state.seventv.resolve_user_by_connection(&platform, &username).await {
+                Ok(Some(user_id)) => user_id,
+                Ok(None) => {
+                    return Err(ApiError::NotFound(format!("No 7TV user found for {} user {}", platform, username)));
+                }
+                Err(e) => {
+                    tracing::error!("Failed to resolve 7TV user {}: {:?}", username, e);
+                    return Err(e.into());
+                }
+            }
+        }
+        (None, None) => {
+            return Err(ApiError::Validation("Either userId or platformUsername is required".to_string()));
+        }
+    };
+
+    if payload.dry_run.unwrap_or(false) {
+        let emotes = state.seventv.fetch_user_emotes(&user_id, limit).await?;
+        let options = ProcessOptions {
+            preferred_scale: payload.preferred_scale,
+            preferred_format: payload.preferred_format,
+            convert_to_png: payload.convert_to_png.unwrap_or(false),
+            overwrite: true,
+        };
+        let batch = preview_emotes(emotes, options, state.seventv.file_rules());
+        return Ok(SearchResponse {
+            success: true,
+            total_found: batch.emotes.len() as i32,
+            emotes: batch.emotes,
+            message: append_note(Some(DRY_RUN_MESSAGE.to_string()), limit_note.as_deref()),
+            cached: Some(false),
+            processing_time: Some(elapsed_secs(start)),
+            page: Some(1),
+            total_pages: Some(1),
+            results_per_page: Some(limit),
+            has_next_page: Some(false),
+            failed_count: Some(batch.failures.len() as i32),
+            animated_count: None,
+            static_count: None,
+            synced_at: None,
+        });
+    }
+
+    ensure_running(state)?;
 
     // 1. Cleanup existing blobs in that folder
     if let Err(e) = state.storage.delete_blobs_by_prefix(&format!("{}/", folder)).await {
         tracing::error!("Failed to cleanup Azure folder {}: {:?}", folder, e);
-        return Json(SearchResponse {
-            success: false,
-            total_found: 0,
-            emotes: vec![],
-            message: Some(format!("Failed to cleanup existing emotes: {}", e)),
-            cached: Some(false),
-            processing_time: None,
-            page: None,
-            total_pages: None,
-            results_per_page: None,
-            has_next_page: None,
-        });
+        return Err(ApiError::Storage(format!("Failed to cleanup existing emotes: {}", e)));
     }
 
-    match state.seventv.fetch_user_emotes(&payload.user_id, limit).await {
+    match state.seventv.fetch_user_emotes(&user_id, limit).await {
         Ok(emotes) => {
-            let processed = state.seventv.process_emotes_batch(emotes, &folder).await;
+            let options = ProcessOptions {
+                preferred_scale: payload.preferred_scale,
+                preferred_format: payload.preferred_format,
+                convert_to_png: payload.convert_to_png.unwrap_or(false),
+                overwrite: true,
+            };
+            let batch = state.seventv.process_emotes_batch(emotes, &folder, options).await;
+            let failed_count = batch.failures.len() as i32;
+            let processed = batch.emotes;
             
             // Save to Redis with a custom key: "user_emotes:{folder_name}"
             let cache_key = format!("user_emotes:{}", folder);
@@ -440,7 +1463,7 @@ async fn sync_user_emotes_handler(
                     emote_count = EXCLUDED.emote_count
                 "#
             )
-            .bind(payload.user_id)
+            .bind(user_id)
             .bind(&folder)
             .bind(user_display_name)
             .bind(emote_count)
@@ -452,134 +1475,347 @@ async fn sync_user_emotes_handler(
             }
 
             // Insert stickers into database
-            for emote in &processed {
-                let _ = sqlx::query(
-                    r#"
-                    INSERT INTO stickers (seven_tv_id, emote_name, file_name, url, owner_name, tags, animated, folder_name)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                    ON CONFLICT (seven_tv_id, folder_name) 
-                    DO UPDATE SET 
-                        emote_name = EXCLUDED.emote_name,
-                        file_name = EXCLUDED.file_name,
-                        url = EXCLUDED.url,
-                        owner_name = EXCLUDED.owner_name,
-                        tags = EXCLUDED.tags,
-                        animated = EXCLUDED.animated
-                    "#
-                )
-                .bind(&emote.emote_id)
-                .bind(&emote.emote_name)
-                .bind(&emote.file_name)
-                .bind(&emote.url)
-                .bind(&emote.owner)
-                .bind(&emote.tags)
-                .bind(emote.animated.unwrap_or(false))
-                .bind(&folder)
-                .execute(&state.db)
-                .await;
-            }
+            upsert_folder_stickers(&state.db, &folder, &processed).await;
 
-            Json(SearchResponse {
+            Ok(SearchResponse {
                 success: true,
                 total_found: processed.len() as i32,
                 emotes: processed,
-                message: Some("User emotes synced successfully".to_string()),
+                message: append_note(Some("User emotes synced successfully".to_string()), limit_note.as_deref()),
                 cached: Some(false),
-                processing_time: None,
+                processing_time: Some(elapsed_secs(start)),
                 page: Some(1),
                 total_pages: Some(1),
                 results_per_page: Some(limit),
                 has_next_page: Some(false),
+                failed_count: Some(failed_count),
+                animated_count: None,
+                static_count: None,
+                synced_at: None,
             })
         },
         Err(e) => {
             tracing::error!("Failed to sync user emotes: {:?}", e);
-            Json(SearchResponse {
-                success: false,
-                total_found: 0,
-                emotes: vec![],
-                message: Some(e.to_string()),
-                cached: Some(false),
-                processing_time: None,
-                page: None,
-                total_pages: None,
-                results_per_page: None,
-                has_next_page: None,
-            })
+            Err(e.into())
         }
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/admin/sync-emote-set",
+    params(jobs::SyncModeQuery),
+    request_body = crate::models::SyncEmoteSetRequest,
+    responses(
+        (status = 200, description = "Sync finished", body = SearchResponse),
+        (status = 202, description = "Sync queued as a background job"),
+        (status = 400, description = "Invalid request", body = SearchResponse),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 404, description = "User or emote set not found on 7TV", body = SearchResponse),
+        (status = 502, description = "7TV request failed", body = SearchResponse),
+        (status = 503, description = "Storage not configured or server shutting down", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+async fn sync_emote_set_handler(
+    State(state): State<Arc<AppState>>,
+    Query(mode): Query<jobs::SyncModeQuery>,
+    Json(payload): Json<crate::models::SyncEmoteSetRequest>,
+) -> Response {
+    if let Err(e) = validate_folder_name(&payload.folder_name) {
+        return e.into_response();
+    }
+
+    let callback = jobs::SyncCallback {
+        url: payload.callback_url.clone(),
+        folder: payload.folder_name.clone(),
+        period: None,
+    };
+
+    jobs::run_sync(state, mode.run_async.unwrap_or(false), "emote_set", callback, move |state| async move {
+        sync_emote_set(&state, payload).await
+    })
+    .await
+}
+
+async fn sync_emote_set(
+    state: &AppState,
+    payload: crate::models::SyncEmoteSetRequest,
+) -> Result<SearchResponse, ApiError> {
+    let start = Instant::now();
+    let (limit, limit_note) = checked_limit(state, payload.limit.unwrap_or(100))?;
+    let folder = payload.folder_name;
+    validate_preferred_scale(payload.preferred_scale)?;
+    ensure_storage(state)?;
+
+    let emote_set = match state.seventv.fetch_emote_set(&payload.emote_set_id, limit).await {
+        Ok(Some(emote_set)) => emote_set,
+        Ok(None) => {
+            return Err(ApiError::NotFound(format!("Emote set {} not found on 7TV", payload.emote_set_id)));
+        }
+        Err(e) => {
+            tracing::error!("Failed to sync emote set: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    // Cleanup existing blobs only once we know the set exists
+    ensure_running(state)?;
+    if let Err(e) = state.storage.delete_blobs_by_prefix(&format!("{}/", folder)).await {
+        tracing::error!("Failed to cleanup Azure folder {}: {:?}", folder, e);
+        return Err(ApiError::Storage(format!("Failed to cleanup existing emotes: {}", e)));
+    }
+
+    let batch = state.seventv.process_emotes_batch(emote_set.emotes, &folder, ProcessOptions {
+        preferred_scale: payload.preferred_scale,
+        preferred_format: None,
+        convert_to_png: payload.convert_to_png.unwrap_or(false),
+        overwrite: true,
+    }).await;
+
+    let failed_count = batch.failures.len() as i32;
+
+    let processed = batch.emotes;
+
+    let cache_key = format!("user_emotes:{}", folder);
+    let ttl = 86400 * 30;
+
+    if let Err(e) = state.cache.save_to_cache(&cache_key, &processed, ttl).await {
+        tracing::error!("Failed to save synced emote set to cache: {:?}", e);
+    }
+
+    let query_result = sqlx::query(
+        r#"
+        INSERT INTO emote_sets (seven_tv_set_id, folder_name, set_name, last_synced_at, emote_count)
+        VALUES ($1, $2, $3, NOW(), $4)
+        ON CONFLICT (folder_name)
+        DO UPDATE SET
+            seven_tv_set_id = EXCLUDED.seven_tv_set_id,
+            set_name = EXCLUDED.set_name,
+            last_synced_at = NOW(),
+            emote_count = EXCLUDED.emote_count
+        "#
+    )
+    .bind(&payload.emote_set_id)
+    .bind(&folder)
+    .bind(&emote_set.name)
+    .bind(processed.len() as i32)
+    .execute(&state.db)
+    .await;
+
+    if let Err(e) = query_result {
+        tracing::error!("Failed to update emote set record in DB: {:?}", e);
+    }
+
+    upsert_folder_stickers(&state.db, &folder, &processed).await;
+
+    Ok(SearchResponse {
+        success: true,
+        total_found: processed.len() as i32,
+        emotes: processed,
+        message: append_note(Some("Emote set synced successfully".to_string()), limit_note.as_deref()),
+        cached: Some(false),
+        processing_time: Some(elapsed_secs(start)),
+        page: Some(1),
+        total_pages: Some(1),
+        results_per_page: Some(limit),
+        has_next_page: Some(false),
+        failed_count: Some(failed_count),
+        animated_count: None,
+        static_count: None,
+        synced_at: None,
+    })
+}
+
+/// Upserts processed emotes as `stickers` rows for a folder. Individual row
+/// failures are ignored so one bad emote doesn't abort the whole sync.
+async fn upsert_folder_stickers(db: &sqlx::PgPool, folder: &str, emotes: &[EmoteResponse]) {
+    for emote in emotes {
+        let _ = upsert_sticker(db, folder, emote).await;
+    }
+}
+
+/// Inserts or refreshes one sticker row, returning `true` when it was new.
+async fn upsert_sticker<'e, E>(db: E, folder: &str, emote: &EmoteResponse) -> Result<bool, sqlx::Error>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    sqlx::query_scalar::<_, bool>(
+        r#"
+        INSERT INTO stickers (seven_tv_id, emote_name, file_name, url, owner_name, tags, animated, folder_name, scale, mime, width, height, zero_width, size_bytes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        ON CONFLICT (seven_tv_id, folder_name) 
+        DO UPDATE SET 
+            emote_name = EXCLUDED.emote_name,
+            file_name = EXCLUDED.file_name,
+            url = EXCLUDED.url,
+            owner_name = EXCLUDED.owner_name,
+            tags = EXCLUDED.tags,
+            animated = EXCLUDED.animated,
+            scale = EXCLUDED.scale,
+            mime = EXCLUDED.mime,
+            width = EXCLUDED.width,
+            height = EXCLUDED.height,
+            zero_width = EXCLUDED.zero_width,
+            size_bytes = EXCLUDED.size_bytes
+        RETURNING (xmax = 0)
+        "#
+    )
+    .bind(&emote.emote_id)
+    .bind(&emote.emote_name)
+    .bind(&emote.file_name)
+    .bind(&emote.url)
+    .bind(&emote.owner)
+    .bind(&emote.tags)
+    .bind(emote.animated.unwrap_or(false))
+    .bind(folder)
+    .bind(emote.scale)
+    .bind(&emote.mime)
+    .bind(emote.width)
+    .bind(emote.height)
+    .bind(emote.zero_width.unwrap_or(false))
+    .bind(emote.size_bytes)
+    .fetch_one(db)
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/user/emotes/saved",
+    tag = "emotes",
+    params(crate::models::SavedUserEmotesQuery),
+    responses(
+        (status = 200, description = "Emotes stored for a folder", body = SearchResponse),
+        (status = 404, description = "No emotes stored for this folder", body = SearchResponse),
+        (status = 422, description = "Invalid fields, listed in `errors`", body = InvalidFieldsResponse)
+    )
+)]
 async fn get_saved_user_emotes_handler(
     State(state): State<Arc<AppState>>,
     Query(params): Query<crate::models::SavedUserEmotesQuery>,
-) -> Json<SearchResponse> {
-    let limit = params.limit.unwrap_or(100) as i64;
-    
-    // Query stickers from database
-    let rows = sqlx::query_as::<_, StickerRow>(
-        "SELECT seven_tv_id, emote_name, file_name, url, owner_name, tags, animated FROM stickers WHERE folder_name = $1 LIMIT $2"
+) -> Result<Json<SearchResponse>, ApiError> {
+    saved_folder_emotes(
+        &state,
+        &params.folder_name,
+        params.limit,
+        params.page,
+        params.offset,
+        params.animated_only,
+        params.static_only,
+        params.include_zero_width,
+        params.max_size_bytes,
     )
-    .bind(&params.folder_name)
-    .bind(limit)
-    .fetch_all(&state.db)
+    .await
+    .map(Json)
+}
+
+/// Resolves a 1-based `page` or a raw row `offset` (which wins) into
+/// `(page, offset)`; an offset between pages reports the page it starts in.
+/// `limit` must already be at least 1.
+fn page_window(limit: i64, page: Option<i32>, offset: Option<i64>) -> Result<(i64, i64), ApiError> {
+    match (offset, page) {
+        (Some(offset), _) if offset < 0 => Err(ApiError::invalid_field("offset", "must be at least 0")),
+        (Some(offset), _) => Ok((offset / limit + 1, offset)),
+        (None, Some(page)) if page < 1 => Err(ApiError::invalid_field("page", "must be at least 1")),
+        (None, page) => {
+            let page = page.unwrap_or(1) as i64;
+            Ok((page, (page - 1) * limit))
+        }
+    }
+}
+
+/// Resolves the `animatedOnly`/`staticOnly` flags of the saved-emote
+/// endpoints; setting both is rejected.
+fn saved_animation_filter(animated_only: Option<bool>, static_only: Option<bool>) -> Result<AnimationFilter, ApiError> {
+    match (animated_only.unwrap_or(false), static_only.unwrap_or(false)) {
+        (true, true) => Err(ApiError::Validation("animatedOnly and staticOnly are mutually exclusive".to_string())),
+        (true, false) => Ok(AnimationFilter::Animated),
+        (false, true) => Ok(AnimationFilter::Static),
+        (false, false) => Ok(AnimationFilter::Any),
+    }
+}
+
+/// Pages through the stored `stickers` rows of one folder.
+#[allow(clippy::too_many_arguments)]
+async fn saved_folder_emotes(
+    state: &AppState,
+    folder_name: &str,
+    limit: Option<i32>,
+    page: Option<i32>,
+    offset: Option<i64>,
+    animated_only: Option<bool>,
+    static_only: Option<bool>,
+    include_zero_width: Option<bool>,
+    max_size_bytes: Option<i32>,
+) -> Result<SearchResponse, ApiError> {
+    let start = Instant::now();
+    let (limit, limit_note) = checked_limit(state, limit.unwrap_or(100))?;
+    let limit = limit as i64;
+    let (page, offset) = page_window(limit, page, offset)?;
+
+    // None keeps the old behaviour of returning both kinds
+    let animated = saved_animation_filter(animated_only, static_only)?.gql_animated();
+    let include_zero_width = include_zero_width.unwrap_or(true);
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM stickers WHERE folder_name = $1 AND ($2::BOOLEAN IS NULL OR animated = $2) AND ($3 OR NOT COALESCE(zero_width, false)) AND ($4::INTEGER IS NULL OR size_bytes <= $4)"
+    )
+    .bind(folder_name)
+    .bind(animated)
+    .bind(include_zero_width)
+    .bind(max_size_bytes)
+    .fetch_one(&state.db)
     .await;
 
+    // Query stickers from database
+    let rows = match total {
+        Ok(total) => sqlx::query_as::<_, StickerRow>(
+            "SELECT seven_tv_id, emote_name, file_name, url, owner_name, tags, animated, scale, mime, width, height, zero_width, size_bytes FROM stickers WHERE folder_name = $1 AND ($2::BOOLEAN IS NULL OR animated = $2) AND ($3 OR NOT COALESCE(zero_width, false)) AND ($4::INTEGER IS NULL OR size_bytes <= $4) ORDER BY id LIMIT $5 OFFSET $6"
+        )
+        .bind(folder_name)
+        .bind(animated)
+        .bind(include_zero_width)
+        .bind(max_size_bytes)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&state.db)
+        .await
+        .map(|stickers| (total, stickers)),
+        Err(e) => Err(e),
+    };
+
     match rows {
-        Ok(stickers) if !stickers.is_empty() => {
-            let emotes: Vec<EmoteResponse> = stickers.into_iter().map(|s| EmoteResponse {
-                emote_id: s.seven_tv_id,
-                emote_name: s.emote_name,
-                file_name: s.file_name,
-                url: s.url,
-                owner: s.owner_name,
-                tags: s.tags,
-                animated: Some(s.animated),
-                scale: None, // We don't store scale in DB yet, but can be added if needed
-                mime: None, // Mime can be inferred or added to DB
-            }).collect();
-
-            Json(SearchResponse {
+        // A filter that matches nothing is an empty page, not a missing folder
+        Ok((total, stickers)) if total > 0 || animated.is_some() || !include_zero_width || max_size_bytes.is_some() => {
+            let emotes: Vec<EmoteResponse> = stickers.into_iter().map(EmoteResponse::from).collect();
+
+            let total_pages = (total + limit - 1) / limit;
+
+            Ok(SearchResponse {
                 success: true,
-                total_found: emotes.len() as i32,
+                total_found: total as i32,
                 emotes,
-                message: None,
+                message: limit_note,
                 cached: Some(false),
-                processing_time: None,
-                page: Some(1),
-                total_pages: Some(1),
+                processing_time: Some(elapsed_secs(start)),
+                page: Some(page as i32),
+                total_pages: Some(total_pages as i32),
                 results_per_page: Some(limit as i32),
-                has_next_page: Some(false),
+                has_next_page: Some(offset + limit < total),
+                failed_count: None,
+                animated_count: None,
+                static_count: None,
+                synced_at: None,
             })
         },
         Ok(_) => {
-            Json(SearchResponse {
-                success: false,
-                total_found: 0,
-                emotes: vec![],
-                message: Some("No saved emotes found for this folder name".to_string()),
-                cached: Some(false),
-                processing_time: None,
-                page: None,
-                total_pages: None,
-                results_per_page: None,
-                has_next_page: None,
-            })
+            Err(ApiError::NotFound("No saved emotes found for this folder name".to_string()))
         },
         Err(e) => {
             tracing::error!("Failed to fetch stickers from DB: {:?}", e);
-            Json(SearchResponse {
-                success: false,
-                total_found: 0,
-                emotes: vec![],
-                message: Some(format!("Database error: {}", e)),
-                cached: Some(false),
-                processing_time: None,
-                page: None,
-                total_pages: None,
-                results_per_page: None,
-                has_next_page: None,
-            })
+            Err(e.into())
         }
     }
 }
@@ -593,9 +1829,36 @@ struct StickerRow {
     owner_name: Option<String>,
     tags: Option<Vec<String>>,
     animated: bool,
+    scale: Option<i32>,
+    mime: Option<String>,
+    width: Option<i32>,
+    height: Option<i32>,
+    zero_width: Option<bool>,
+    size_bytes: Option<i32>,
+}
+
+impl From<StickerRow> for EmoteResponse {
+    fn from(s: StickerRow) -> Self {
+        EmoteResponse {
+            page_url: Some(EmoteResponse::seventv_page_url(&s.seven_tv_id)),
+            emote_id: s.seven_tv_id,
+            emote_name: s.emote_name,
+            file_name: s.file_name,
+            url: s.url,
+            owner: s.owner_name,
+            tags: s.tags,
+            animated: Some(s.animated),
+            scale: s.scale,
+            mime: s.mime,
+            width: s.width,
+            height: s.height,
+            zero_width: s.zero_width,
+            size_bytes: s.size_bytes,
+        }
+    }
 }
 
-#[derive(Serialize, sqlx::FromRow)]
+#[derive(Serialize, sqlx::FromRow, ToSchema)]
 struct UserRecord {
     id: i32,
     seven_tv_id: String,
@@ -606,32 +1869,426 @@ struct UserRecord {
 }
 
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct UsersListResponse {
     success: bool,
     users: Vec<UserRecord>,
+    total: i64,
+    page: i64,
+    limit: i64,
+    total_pages: i64,
+}
+
+const MAX_USERS_PAGE_SIZE: i64 = 200;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct UsersQuery {
+    /// 1-based page number (default 1).
+    page: Option<i64>,
+    /// Users per page (default 50, capped at 200).
+    limit: Option<i64>,
+    /// Case-insensitive substring match on `display_name` or `folder_name`.
+    q: Option<String>,
+    /// `last_synced` (default), `emote_count` or `display_name`.
+    sort: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/admin/users",
+    params(UsersQuery),
+    responses(
+        (status = 200, description = "Synced users", body = UsersListResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 422, description = "Unknown sort or out-of-range page, listed in `errors`", body = InvalidFieldsResponse),
+        (status = 500, description = "Database failure", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
 async fn list_users_handler(
     State(state): State<Arc<AppState>>,
-) -> Json<UsersListResponse> {
-    let rows = sqlx::query_as::<_, UserRecord>(
-        "SELECT id, seven_tv_id, folder_name, display_name, last_synced_at, emote_count FROM users ORDER BY last_synced_at DESC"
+    Query(params): Query<UsersQuery>,
+) -> Result<Json<UsersListResponse>, ApiError> {
+    let limit = params.limit.unwrap_or(50).clamp(1, MAX_USERS_PAGE_SIZE);
+    let page = params.page.unwrap_or(1);
+
+    let mut errors = Vec::new();
+    // A huge page would overflow the offset rather than just return nothing
+    let offset = match (page >= 1).then(|| (page - 1).checked_mul(limit)).flatten() {
+        Some(offset) => offset,
+        None => {
+            errors.push(FieldError::new("page", format!("must be between 1 and {}", i64::MAX / limit)));
+            0
+        }
+    };
+    let pattern = params.q.as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(|q| format!("%{}%", stickers::escape_like(q)));
+
+    let order_by = match params.sort.as_deref().unwrap_or("last_synced") {
+        "last_synced" => "last_synced_at DESC NULLS LAST",
+        "emote_count" => "emote_count DESC NULLS LAST",
+        "display_name" => "display_name ASC",
+        other => {
+            errors.push(FieldError::new(
+                "sort",
+                format!("unknown value '{}'; expected one of last_synced, emote_count, display_name", other),
+            ));
+            ""
+        }
+    };
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    const FILTER: &str = "WHERE ($1::TEXT IS NULL OR display_name ILIKE $1 OR folder_name ILIKE $1)";
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM users {}", FILTER))
+        .bind(&pattern)
+        .fetch_one(&state.db)
+        .await;
+
+    let rows = match total {
+        Ok(total) => sqlx::query_as::<_, UserRecord>(&format!(
+            "SELECT id, seven_tv_id, folder_name, display_name, last_synced_at, emote_count FROM users {} ORDER BY {}, id LIMIT $2 OFFSET $3",
+            FILTER, order_by
+        ))
+        .bind(&pattern)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&state.db)
+        .await
+        .map(|users| (total, users)),
+        Err(e) => Err(e),
+    };
+
+    match rows {
+        Ok((total, users)) => Ok(Json(UsersListResponse {
+            success: true,
+            users,
+            total,
+            page,
+            limit,
+            total_pages: (total + limit - 1) / limit,
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch users: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct DeleteFolderResponse {
+    success: bool,
+    folder_name: String,
+    blobs_deleted: usize,
+    stickers_deleted: u64,
+    users_deleted: u64,
+    emote_sets_deleted: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/users/{folder_name}",
+    params(("folder_name" = String, Path, description = "Folder the user was synced into")),
+    responses(
+        (status = 200, description = "Folder deleted", body = DeleteFolderResponse),
+        (status = 400, description = "Folder is managed by the trending or global sync", body = DeleteFolderResponse),
+        (status = 404, description = "Folder not found", body = DeleteFolderResponse),
+        (status = 401, description = "Missing or invalid admin credentials")
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+async fn delete_user_folder_handler(
+    State(state): State<Arc<AppState>>,
+    Path(folder_name): Path<String>,
+) -> (StatusCode, Json<DeleteFolderResponse>) {
+    let mut response = DeleteFolderResponse {
+        success: false,
+        folder_name: folder_name.clone(),
+        blobs_deleted: 0,
+        stickers_deleted: 0,
+        users_deleted: 0,
+        emote_sets_deleted: 0,
+        message: None,
+    };
+
+    // Trending and global folders are managed by their syncs, never by user
+    // deletion. Other names may predate `validate_folder_name`, so only the
+    // reserved ones are refused here.
+    if folder_name.starts_with("trending/") || folder_name.starts_with("trending_sync:") {
+        response.message = Some("Refusing to delete a trending folder".to_string());
+        return (StatusCode::BAD_REQUEST, Json(response));
+    }
+    if folder_name == global::GLOBAL_FOLDER {
+        response.message = Some("Refusing to delete the global emote folder".to_string());
+        return (StatusCode::BAD_REQUEST, Json(response));
+    }
+
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE folder_name = $1) OR EXISTS(SELECT 1 FROM stickers WHERE folder_name = $1) OR EXISTS(SELECT 1 FROM emote_sets WHERE folder_name = $1)"
+    )
+    .bind(&folder_name)
+    .fetch_one(&state.db)
+    .await;
+
+    match exists {
+        Ok(true) => {}
+        Ok(false) => {
+            response.message = Some("Folder not found".to_string());
+            return (StatusCode::NOT_FOUND, Json(response));
+        }
+        Err(e) => {
+            tracing::error!("Failed to look up folder {}: {:?}", folder_name, e);
+            response.message = Some(format!("Database error: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    }
+
+    // Rows are deleted first but only committed once the blobs are gone, so a
+    // storage failure leaves the DB still pointing at whatever blobs remain
+    let mut tx = match state.db.begin().await {
+        Ok(tx) => tx,
+        Err(e) => {
+            tracing::error!("Failed to start transaction for folder {}: {:?}", folder_name, e);
+            response.message = Some(format!("Database error: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    };
+
+    match delete_folder_rows(&mut tx, &folder_name).await {
+        Ok((stickers, users, emote_sets)) => {
+            response.stickers_deleted = stickers;
+            response.users_deleted = users;
+            response.emote_sets_deleted = emote_sets;
+        }
+        Err(e) => {
+            tracing::error!("Failed to delete DB rows for folder {}: {:?}", folder_name, e);
+            response.message = Some(format!("Database error: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    }
+
+    match state.storage.delete_blobs_by_prefix(&format!("{}/", folder_name)).await {
+        Ok(count) => response.blobs_deleted = count,
+        Err(e) => {
+            tracing::error!("Failed to delete Azure folder {}, rolling back: {:?}", folder_name, e);
+            if let Err(e) = tx.rollback().await {
+                tracing::error!("Rollback for folder {} failed: {:?}", folder_name, e);
+            }
+            response.stickers_deleted = 0;
+            response.users_deleted = 0;
+            response.emote_sets_deleted = 0;
+            response.message = Some(format!("Failed to delete stored emotes, database left unchanged: {}", e));
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+        }
+    }
+
+    if let Err(e) = tx.commit().await {
+        tracing::error!("Blobs for folder {} deleted but commit failed: {:?}", folder_name, e);
+        response.stickers_deleted = 0;
+        response.users_deleted = 0;
+        response.emote_sets_deleted = 0;
+        response.message = Some(format!("Stored emotes deleted but database commit failed: {}", e));
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(response));
+    }
+
+    let cache_key = format!("user_emotes:{}", folder_name);
+    if let Err(e) = state.cache.delete_key(&cache_key).await {
+        tracing::error!("Failed to purge cache key {}: {:?}", cache_key, e);
+    }
+
+    response.success = true;
+    response.message = Some("Folder deleted successfully".to_string());
+    (StatusCode::OK, Json(response))
+}
+
+/// Older alias of `DELETE /api/admin/users/{folder_name}`.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/user-sync/{folder_name}",
+    params(("folder_name" = String, Path, description = "Folder the user was synced into")),
+    responses(
+        (status = 200, description = "Folder deleted", body = DeleteFolderResponse),
+        (status = 400, description = "Folder is managed by the trending or global sync", body = DeleteFolderResponse),
+        (status = 404, description = "Folder not found", body = DeleteFolderResponse),
+        (status = 401, description = "Missing or invalid admin credentials")
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+async fn delete_user_sync_handler(
+    state: State<Arc<AppState>>,
+    folder_name: Path<String>,
+) -> (StatusCode, Json<DeleteFolderResponse>) {
+    delete_user_folder_handler(state, folder_name).await
+}
+
+/// Deletes the `stickers`, `users` and `emote_sets` rows for a folder inside
+/// `tx`, returning how many of each were deleted. The caller commits.
+async fn delete_folder_rows(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    folder_name: &str,
+) -> Result<(u64, u64, u64), sqlx::Error> {
+    let stickers = sqlx::query("DELETE FROM stickers WHERE folder_name = $1")
+        .bind(folder_name)
+        .execute(&mut **tx)
+        .await?;
+    let users = sqlx::query("DELETE FROM users WHERE folder_name = $1")
+        .bind(folder_name)
+        .execute(&mut **tx)
+        .await?;
+    let emote_sets = sqlx::query("DELETE FROM emote_sets WHERE folder_name = $1")
+        .bind(folder_name)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok((stickers.rows_affected(), users.rows_affected(), emote_sets.rows_affected()))
+}
+
+#[derive(Serialize, sqlx::FromRow, ToSchema)]
+struct EmoteSetRecord {
+    id: i32,
+    seven_tv_set_id: String,
+    folder_name: String,
+    set_name: String,
+    last_synced_at: Option<chrono::DateTime<chrono::Utc>>,
+    emote_count: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct EmoteSetsListResponse {
+    success: bool,
+    emote_sets: Vec<EmoteSetRecord>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/emote-sets",
+    responses(
+        (status = 200, description = "Synced emote sets", body = EmoteSetsListResponse),
+        (status = 401, description = "Missing or invalid admin credentials")
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+async fn list_emote_sets_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<EmoteSetsListResponse>, (StatusCode, Json<EmoteSetsListResponse>)> {
+    let rows = sqlx::query_as::<_, EmoteSetRecord>(
+        "SELECT id, seven_tv_set_id, folder_name, set_name, last_synced_at, emote_count FROM emote_sets ORDER BY last_synced_at DESC"
     )
     .fetch_all(&state.db)
     .await;
 
     match rows {
-        Ok(users) => Json(UsersListResponse {
+        Ok(emote_sets) => Ok(Json(EmoteSetsListResponse {
             success: true,
-            users,
-        }),
+            emote_sets,
+        })),
         Err(e) => {
-            tracing::error!("Failed to fetch users: {:?}", e);
-            Json(UsersListResponse {
-                success: false,
-                users: vec![],
-            })
+            tracing::error!("Failed to fetch emote sets: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(EmoteSetsListResponse {
+                    success: false,
+                    emote_sets: vec![],
+                }),
+            ))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::ServiceExt;
+
+    #[test]
+    fn saved_animation_filter_without_flags_is_any() {
+        assert_eq!(saved_animation_filter(None, None).unwrap(), AnimationFilter::Any);
+        assert_eq!(saved_animation_filter(Some(false), Some(false)).unwrap(), AnimationFilter::Any);
+    }
+
+    #[test]
+    fn saved_animation_filter_animated_only() {
+        assert_eq!(saved_animation_filter(Some(true), None).unwrap(), AnimationFilter::Animated);
+        assert_eq!(saved_animation_filter(Some(true), Some(false)).unwrap(), AnimationFilter::Animated);
+    }
+
+    #[test]
+    fn saved_animation_filter_static_only() {
+        assert_eq!(saved_animation_filter(None, Some(true)).unwrap(), AnimationFilter::Static);
+        assert_eq!(saved_animation_filter(Some(false), Some(true)).unwrap(), AnimationFilter::Static);
+    }
+
+    #[test]
+    fn saved_animation_filter_rejects_both_flags() {
+        assert!(matches!(saved_animation_filter(Some(true), Some(true)), Err(ApiError::Validation(_))));
+    }
+
+    // Well above the 32-byte minimum the default predicate compresses
+    const BODY: &str = "{\"success\":true,\"emotes\":[\"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\"]}";
+
+    /// The real split between compressed and uncompressed routes, with stub
+    /// handlers. The blob stub answers with a compressible content type so
+    /// only the routing keeps it uncompressed.
+    fn compression_router() -> Router {
+        let api = Router::new().route(
+            "/api/json",
+            get(|| async { ([(header::CONTENT_TYPE, "application/json")], BODY) }),
+        );
+        let blob_routes = Router::new().route(
+            BLOB_ROUTE,
+            get(|| async { ([(header::CONTENT_TYPE, "text/plain")], BODY) }),
+        );
+        compress_except(api, blob_routes)
+    }
+
+    async fn get_with_encoding(uri: &str, accept_encoding: Option<&str>) -> Response {
+        let mut request = axum::http::Request::builder().uri(uri);
+        if let Some(encoding) = accept_encoding {
+            request = request.header(header::ACCEPT_ENCODING, encoding);
+        }
+        compression_router()
+            .oneshot(request.body(axum::body::Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body_bytes(response: Response) -> Vec<u8> {
+        axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()
+    }
+
+    #[tokio::test]
+    async fn json_routes_are_gzipped_when_accepted() {
+        let response = get_with_encoding("/api/json", Some("gzip")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_ne!(body_bytes(response).await, BODY.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn json_routes_are_plain_without_accept_encoding() {
+        let response = get_with_encoding("/api/json", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(body_bytes(response).await, BODY.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn blob_route_is_never_compressed() {
+        let response = get_with_encoding("/emotes/folder/file.webp", Some("gzip, br")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(body_bytes(response).await, BODY.as_bytes());
+    }
+}
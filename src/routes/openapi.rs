@@ -0,0 +1,131 @@
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::root_handler,
+        super::health::health_handler,
+        super::health::ready_handler,
+        super::health::metrics_handler,
+        super::dashboard::dashboard_handler,
+        super::search_emotes_handler,
+        super::search_emotes_get_handler,
+        super::get_emote_handler,
+        super::get_emote_variants_handler,
+        super::blobs::serve_emote_handler,
+        super::trending_emotes_handler,
+        super::synced_trending_emotes_handler,
+        super::trending_periods_handler,
+        super::ws::trending_ws_handler,
+        super::get_saved_user_emotes_handler,
+        super::stickers::search_stickers_handler,
+        super::folders::list_folders_handler,
+        super::archive::folder_archive_handler,
+        super::folders::folder_manifest_handler,
+        super::export::export_stickers_csv_handler,
+        super::sync_trending_handler,
+        super::sync_trending_stream_handler,
+        super::sync_user_emotes_handler,
+        super::resync_user_handler,
+        super::stats::stats_handler,
+        super::sync_emote_set_handler,
+        super::bulk_sync::sync_all_users_handler,
+        super::bulk_sync::sync_users_batch_handler,
+        super::global::sync_global_handler,
+        super::global::global_emotes_handler,
+        super::jobs::list_sync_jobs_handler,
+        super::jobs::get_sync_job_handler,
+        super::cache::clear_cache_handler,
+        super::list_users_handler,
+        super::list_emote_sets_handler,
+        super::delete_user_folder_handler,
+        super::delete_user_sync_handler,
+        super::folders::rename_folder_handler,
+        super::folders::add_folder_emote_handler,
+        super::folders::remove_folder_emote_handler,
+        super::folders::folder_diff_handler,
+        super::folders::import_folder_handler,
+    ),
+    components(schemas(
+        crate::models::EmoteResponse,
+        crate::models::EmoteVariant,
+        crate::models::EmoteVariantsResponse,
+        crate::models::SearchResponse,
+        crate::models::FieldError,
+        crate::models::ErrorResponse,
+        crate::models::InvalidFieldsResponse,
+        crate::models::SearchRequest,
+        crate::models::ImageFormat,
+        crate::models::TagMatch,
+        crate::models::AnimationFilter,
+        super::BatchSearchResponse,
+        crate::models::TrendingPeriod,
+        crate::models::TrendingPeriodInfo,
+        crate::models::TrendingPeriodsResponse,
+        crate::models::SyncTrendingRequest,
+        crate::models::SyncUserEmotesRequest,
+        crate::models::SyncEmoteSetRequest,
+        crate::models::ResyncUserRequest,
+        crate::models::SyncWebhookPayload,
+        super::bulk_sync::SyncAllUsersRequest,
+        super::bulk_sync::SyncAllUsersResponse,
+        super::bulk_sync::UserSyncSummary,
+        super::bulk_sync::BatchUserEntry,
+        super::bulk_sync::SyncUsersBatchRequest,
+        super::bulk_sync::BatchUserResult,
+        super::bulk_sync::SyncUsersBatchResponse,
+        super::global::SyncGlobalRequest,
+        super::UserRecord,
+        super::UsersListResponse,
+        super::DeleteFolderResponse,
+        super::EmoteSetRecord,
+        super::EmoteSetsListResponse,
+        super::jobs::SyncJob,
+        super::jobs::SyncJobsListResponse,
+        super::folders::FolderSummary,
+        super::folders::FoldersListResponse,
+        super::folders::RenameFolderRequest,
+        super::folders::RenameFolderResponse,
+        super::folders::AddFolderEmoteRequest,
+        super::folders::RemoveFolderEmoteResponse,
+        super::folders::FolderDiffResponse,
+        super::stats::StatsResponse,
+        super::stats::FolderStickerCount,
+        super::folders::DiffEmote,
+        super::folders::RenamedEmote,
+        super::folders::ImportFolderResponse,
+        super::folders::SkippedImportEntry,
+        super::health::ReadinessResponse,
+        super::cache::ClearCacheRequest,
+        super::cache::ClearCacheResponse,
+    )),
+    modifiers(&AdminApiKey),
+    tags(
+        (name = "emotes", description = "Search and fetch 7TV emotes"),
+        (name = "trending", description = "Trending emotes, live and synced"),
+        (name = "folders", description = "Stored emote folders"),
+        (name = "admin", description = "Sync and management routes; require `X-Api-Key` or a bearer token"),
+        (name = "meta", description = "Service info and probes"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the `X-Api-Key` and bearer schemes accepted by the admin routes.
+struct AdminApiKey;
+
+impl Modify for AdminApiKey {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Api-Key"))),
+        );
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::AppState;
+
+/// Length of a rate-limit window; `RATE_LIMIT_PER_MINUTE` requests are allowed per window.
+const WINDOW_SECS: u64 = 60;
+
+fn client_ip(request: &Request, trust_proxy_headers: bool) -> Option<IpAddr> {
+    if trust_proxy_headers {
+        if let Some(ip) = forwarded_for(request.headers()) {
+            return Some(ip);
+        }
+    }
+
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
+
+/// First (client-most) address in `X-Forwarded-For`.
+fn forwarded_for(headers: &HeaderMap) -> Option<IpAddr> {
+    headers
+        .get("x-forwarded-for")?
+        .to_str()
+        .ok()?
+        .split(',')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Fixed-window per-IP limiter. Counters live in Redis so the limit is shared
+/// by every server instance; if Redis is unreachable requests are let through.
+pub async fn limit_by_ip(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let limit = state.config.rate_limit_per_minute;
+    if limit == 0 {
+        return next.run(request).await;
+    }
+
+    let Some(ip) = client_ip(&request, state.config.trust_proxy_headers) else {
+        return next.run(request).await;
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let window = now / WINDOW_SECS;
+    let key = crate::services::cache::CacheService::get_rate_limit_key(&ip.to_string(), window);
+
+    let count = match state.cache.increment_counter(&key, WINDOW_SECS).await {
+        Ok(count) => count,
+        Err(e) => {
+            tracing::warn!("Rate limiter unavailable, allowing request: {}", e);
+            return next.run(request).await;
+        }
+    };
+
+    if count > u64::from(limit) {
+        let retry_after = WINDOW_SECS - now % WINDOW_SECS;
+
+        tracing::warn!("Rate limit exceeded for {}", ip);
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            Json(serde_json::json!({
+                "success": false,
+                "message": "Rate limit exceeded, please slow down",
+                "retryAfter": retry_after,
+            })),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
@@ -0,0 +1,148 @@
+use axum::{
+    extract::State,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use crate::models::ApiError;
+use crate::AppState;
+
+const STATS_CACHE_KEY: &str = "admin_stats";
+// Blob listing and SCANs are slow, so the dashboard sees slightly stale numbers
+const STATS_CACHE_TTL: u64 = 60;
+
+/// Redis key prefixes this service writes, reported in `cacheKeys`.
+const CACHE_NAMESPACES: &[&str] = &[
+    "emote_search",
+    "emote",
+    "emote_variants",
+    "trending",
+    "trending_sync",
+    "user_emotes",
+    "rate_limit",
+];
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderStickerCount {
+    folder_name: String,
+    sticker_count: i64,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsResponse {
+    success: bool,
+    total_stickers: i64,
+    animated_stickers: i64,
+    static_stickers: i64,
+    folders: Vec<FolderStickerCount>,
+    user_count: i64,
+    last_user_sync_at: Option<chrono::DateTime<chrono::Utc>>,
+    last_emote_set_sync_at: Option<chrono::DateTime<chrono::Utc>>,
+    last_job_finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Key count per Redis namespace; null when Redis is unreachable.
+    cache_keys: Option<BTreeMap<String, usize>>,
+    /// Bytes stored in the blob container; null when storage is unavailable.
+    total_bytes: Option<u64>,
+    generated_at: chrono::DateTime<chrono::Utc>,
+    cached: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/stats",
+    responses(
+        (status = 200, description = "Aggregate counts for the admin dashboard, cached for 60 seconds", body = StatsResponse),
+        (status = 401, description = "Missing or invalid admin credentials"),
+        (status = 500, description = "Database failure", body = SearchResponse)
+    ),
+    security(("api_key" = []), ("bearer" = [])),
+    tag = "admin",
+)]
+pub async fn stats_handler(State(state): State<Arc<AppState>>) -> Result<Json<StatsResponse>, ApiError> {
+    if let Some(cached_data) = state.cache.get_from_cache(STATS_CACHE_KEY).await {
+        if let Ok(mut stats) = serde_json::from_slice::<StatsResponse>(&cached_data) {
+            state.metrics.record_cache_lookup("admin_stats", true);
+            stats.cached = true;
+            return Ok(Json(stats));
+        }
+    }
+    state.metrics.record_cache_lookup("admin_stats", false);
+
+    let (total_stickers, animated_stickers, static_stickers): (i64, i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*),
+               COUNT(*) FILTER (WHERE animated),
+               COUNT(*) FILTER (WHERE NOT animated)
+        FROM stickers
+        "#
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    let folders = sqlx::query_as::<_, FolderStickerCount>(
+        "SELECT folder_name, COUNT(*) AS sticker_count FROM stickers GROUP BY folder_name ORDER BY sticker_count DESC, folder_name"
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let (user_count, last_user_sync_at): (i64, Option<chrono::DateTime<chrono::Utc>>) =
+        sqlx::query_as("SELECT COUNT(*), MAX(last_synced_at) FROM users")
+            .fetch_one(&state.db)
+            .await?;
+    let last_emote_set_sync_at: Option<chrono::DateTime<chrono::Utc>> =
+        sqlx::query_scalar("SELECT MAX(last_synced_at) FROM emote_sets")
+            .fetch_one(&state.db)
+            .await?;
+    let last_job_finished_at: Option<chrono::DateTime<chrono::Utc>> =
+        sqlx::query_scalar("SELECT MAX(finished_at) FROM sync_jobs")
+            .fetch_one(&state.db)
+            .await?;
+
+    let stats = StatsResponse {
+        success: true,
+        total_stickers,
+        animated_stickers,
+        static_stickers,
+        folders,
+        user_count,
+        last_user_sync_at,
+        last_emote_set_sync_at,
+        last_job_finished_at,
+        cache_keys: count_cache_keys(&state).await,
+        total_bytes: match state.storage.total_size("").await {
+            Ok(total) => Some(total),
+            Err(e) => {
+                tracing::warn!("Failed to sum blob sizes for stats: {:?}", e);
+                None
+            }
+        },
+        generated_at: chrono::Utc::now(),
+        cached: false,
+    };
+
+    if let Err(e) = state.cache.save_to_cache(STATS_CACHE_KEY, &stats, STATS_CACHE_TTL).await {
+        tracing::error!("Failed to cache admin stats: {:?}", e);
+    }
+
+    Ok(Json(stats))
+}
+
+async fn count_cache_keys(state: &AppState) -> Option<BTreeMap<String, usize>> {
+    let mut counts = BTreeMap::new();
+    for namespace in CACHE_NAMESPACES {
+        match state.cache.count_keys(&format!("{}:*", namespace)).await {
+            Ok(count) => {
+                counts.insert(namespace.to_string(), count);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to count Redis keys for stats: {:?}", e);
+                return None;
+            }
+        }
+    }
+    Some(counts)
+}
@@ -0,0 +1,96 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use std::sync::Arc;
+use std::time::Instant;
+use crate::AppState;
+use crate::models::{ApiError, EmoteResponse, SearchResponse, StickerSearchQuery};
+use super::StickerRow;
+
+/// Escapes `%`, `_` and `\` so user input is matched literally by `ILIKE`.
+pub(super) fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stickers/search",
+    tag = "emotes",
+    params(StickerSearchQuery),
+    responses(
+        (status = 200, description = "Saved stickers matching the query", body = SearchResponse),
+        (status = 400, description = "Empty query", body = SearchResponse)
+    )
+)]
+pub async fn search_stickers_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<StickerSearchQuery>,
+) -> Result<Json<SearchResponse>, ApiError> {
+    let start = Instant::now();
+    let term = params.q.trim();
+    if term.is_empty() {
+        return Err(ApiError::Validation("q must not be empty".to_string()));
+    }
+
+    let limit = params.limit.unwrap_or(20).max(1) as i64;
+    let page = params.page.unwrap_or(1).max(1) as i64;
+    let offset = (page - 1) * limit;
+    let animated_only = params.animated_only.unwrap_or(false);
+    let name_pattern = format!("%{}%", escape_like(term));
+    let tag = term.to_lowercase();
+
+    const FILTER: &str = r#"
+        WHERE (emote_name ILIKE $1 OR tags @> ARRAY[$2])
+          AND ($3::TEXT IS NULL OR folder_name = $3)
+          AND (NOT $4 OR animated)
+    "#;
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM stickers {}", FILTER))
+        .bind(&name_pattern)
+        .bind(&tag)
+        .bind(&params.folder_name)
+        .bind(animated_only)
+        .fetch_one(&state.db)
+        .await?;
+
+    let stickers = sqlx::query_as::<_, StickerRow>(&format!(
+        "SELECT seven_tv_id, emote_name, file_name, url, owner_name, tags, animated, scale, mime, width, height, zero_width, size_bytes FROM stickers {} ORDER BY emote_name, id LIMIT $5 OFFSET $6",
+        FILTER
+    ))
+    .bind(&name_pattern)
+    .bind(&tag)
+    .bind(&params.folder_name)
+    .bind(animated_only)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    let emotes: Vec<EmoteResponse> = stickers.into_iter().map(EmoteResponse::from).collect();
+    let total_pages = (total + limit - 1) / limit;
+
+    Ok(Json(SearchResponse {
+        success: true,
+        total_found: total as i32,
+        emotes,
+        message: None,
+        cached: Some(false),
+        processing_time: Some(super::elapsed_secs(start)),
+        page: Some(page as i32),
+        total_pages: Some(total_pages as i32),
+        results_per_page: Some(limit as i32),
+        has_next_page: Some(page < total_pages),
+        failed_count: None,
+        animated_count: None,
+        static_count: None,
+        synced_at: None,
+    }))
+}
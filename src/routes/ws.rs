@@ -0,0 +1,51 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use crate::AppState;
+
+/// Pushes a `SearchResponse` JSON frame to every connected client each time
+/// a trending sync finishes.
+#[utoipa::path(
+    get,
+    path = "/ws/trending",
+    tag = "trending",
+    responses((status = 101, description = "WebSocket; each text frame is a `SearchResponse` for a finished trending sync"))
+)]
+pub async fn trending_ws_handler(
+    State(state): State<Arc<AppState>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| forward_trending_updates(socket, state))
+}
+
+async fn forward_trending_updates(mut socket: WebSocket, state: Arc<AppState>) {
+    let mut updates = state.trending_updates.subscribe();
+
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(json) => {
+                    if socket.send(Message::Text(json.as_str().to_owned())).await.is_err() {
+                        break;
+                    }
+                }
+                // A slow client misses the oldest updates rather than holding up everyone else
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!("Trending WebSocket client lagged, skipped {} updates", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by axum; anything else from the client is ignored
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
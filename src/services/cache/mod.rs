@@ -1,37 +1,122 @@
 use crate::config::Config;
 use redis::AsyncCommands;
 use serde::Serialize;
+use crate::services::error::ServiceError;
+use crate::services::metrics::Metrics;
+use crate::models::{AnimationFilter, SearchSort};
+use crate::services::seventv::SearchFilters;
+use std::sync::Arc;
 
 pub struct CacheService {
     client: redis::Client,
+    metrics: Arc<Metrics>,
 }
 
 impl CacheService {
-    pub fn new(cfg: &Config) -> Self {
+    pub fn new(cfg: &Config, metrics: Arc<Metrics>) -> Self {
         let client = if !cfg.redis_url.is_empty() {
             redis::Client::open(cfg.redis_url.clone()).expect("Failed to open redis client")
         } else {
             let addr = format!("redis://{}:{}", cfg.redis_host, cfg.redis_port);
             redis::Client::open(addr).expect("Failed to open redis client")
         };
-        Self { client }
+        Self { client, metrics }
     }
 
-    pub fn get_cache_key(query: &str, limit: i32, animated_only: bool) -> String {
-        format!("emote_search:{}:{}:{}", query, limit, animated_only)
+    /// Optional parts are appended only when set, so plain searches keep their old keys.
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_cache_key(
+        query: &str,
+        limit: i32,
+        page: i32,
+        animation: AnimationFilter,
+        owner: Option<&str>,
+        format: Option<&str>,
+        scale: Option<i32>,
+        filters: &SearchFilters,
+    ) -> String {
+        let mut key = format!("emote_search:{}:{}:{}:{}", query, limit, page, animation.as_str());
+        if let Some(owner) = owner {
+            key.push_str(&format!(":owner={}", owner.to_lowercase()));
+        }
+        if let Some(format) = format {
+            key.push_str(&format!(":format={}", format));
+        }
+        if let Some(scale) = scale {
+            key.push_str(&format!(":scale={}", scale));
+        }
+        if !filters.tags.is_empty() {
+            key.push_str(&format!(":tags={}:{}", filters.tag_match.as_gql(), filters.tags.join(",")));
+        }
+        if filters.exact_match {
+            key.push_str(if filters.case_sensitive { ":exact=cs" } else { ":exact=ci" });
+        }
+        if filters.sort != SearchSort::default() {
+            key.push_str(&format!(":sort={}", filters.sort.as_gql()));
+        }
+        if filters.exclude_zero_width {
+            key.push_str(":no_zw");
+        }
+        key
     }
 
-    pub fn get_trending_cache_key(period: &str, limit: i32, page: i32, animated_only: bool) -> String {
-        format!("trending:{}:{}:{}:{}", period, limit, page, animated_only)
+    pub fn get_emote_cache_key(emote_id: &str) -> String {
+        format!("emote:{}", emote_id)
+    }
+
+    pub fn get_emote_variants_cache_key(emote_id: &str) -> String {
+        format!("emote_variants:{}", emote_id)
+    }
+
+    /// `tags` should already be normalized; an empty list keeps the untagged key.
+    pub fn get_trending_cache_key(period: &str, limit: i32, page: i32, animation: AnimationFilter, tags: &[String]) -> String {
+        if tags.is_empty() {
+            format!("trending:{}:{}:{}:{}", period, limit, page, animation.as_str())
+        } else {
+            format!("trending:{}:{}:{}:{}:tags={}", period, limit, page, animation.as_str(), tags.join(","))
+        }
     }
 
     pub fn get_trending_sync_key(period: &str, animated_only: bool) -> String {
         format!("trending_sync:{}:{}", period, animated_only)
     }
 
+    pub fn get_rate_limit_key(client: &str, window: u64) -> String {
+        format!("rate_limit:{}:{}", client, window)
+    }
+
+    /// Increments a counter and returns the new value. The TTL is reset on
+    /// every call, so the key expires `ttl_seconds` after its last increment;
+    /// callers that need fixed windows put the window in the key.
+    pub async fn increment_counter(&self, key: &str, ttl_seconds: u64) -> Result<u64, ServiceError> {
+        let mut conn = self.client.get_multiplexed_tokio_connection().await?;
+        let (count,): (u64,) = redis::pipe()
+            .atomic()
+            .incr(key, 1)
+            .expire(key, ttl_seconds as i64)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+        Ok(count)
+    }
+
+    pub async fn ping(&self) -> Result<(), ServiceError> {
+        let mut conn = self.client.get_multiplexed_tokio_connection().await?;
+        redis::cmd("PING").query_async::<String>(&mut conn).await?;
+        Ok(())
+    }
+
     pub async fn get_from_cache(&self, key: &str) -> Option<Vec<u8>> {
-        let mut conn = self.client.get_multiplexed_tokio_connection().await.ok()?;
-        conn.get(key).await.ok()
+        let result: redis::RedisResult<Option<Vec<u8>>> = async {
+            let mut conn = self.client.get_multiplexed_tokio_connection().await?;
+            conn.get(key).await
+        }
+        .await;
+
+        result.unwrap_or_else(|_| {
+            self.metrics.record_cache_error();
+            None
+        })
     }
 
     pub async fn save_to_cache<T: Serialize>(
@@ -39,19 +124,53 @@ impl CacheService {
         key: &str,
         data: &T,
         ttl_seconds: u64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut conn = self.client.get_multiplexed_tokio_connection().await?;
+    ) -> Result<(), ServiceError> {
         let bytes = serde_json::to_vec(data)?;
-        conn.set_ex::<_, _, ()>(key, bytes, ttl_seconds).await?;
+        let result: redis::RedisResult<()> = async {
+            let mut conn = self.client.get_multiplexed_tokio_connection().await?;
+            conn.set_ex(key, bytes, ttl_seconds).await
+        }
+        .await;
+
+        if result.is_err() {
+            self.metrics.record_cache_error();
+        }
+        Ok(result?)
+    }
+
+    pub async fn delete_key(&self, key: &str) -> Result<(), ServiceError> {
+        let mut conn = self.client.get_multiplexed_tokio_connection().await?;
+        conn.del::<_, ()>(key).await?;
         Ok(())
     }
 
-    pub async fn clear_cache(&self, pattern: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// Counts keys matching `pattern` (Redis glob syntax) using SCAN.
+    pub async fn count_keys(&self, pattern: &str) -> Result<usize, ServiceError> {
         let mut conn = self.client.get_multiplexed_tokio_connection().await?;
-        let keys: Vec<String> = conn.keys(pattern).await?;
-        if !keys.is_empty() {
-            conn.del::<_, ()>(keys).await?;
+        let mut iter: redis::AsyncIter<String> = conn.scan_match(pattern).await?;
+        let mut count = 0;
+        while iter.next_item().await.is_some() {
+            count += 1;
         }
-        Ok(())
+        Ok(count)
+    }
+
+    /// Deletes every key matching `pattern` (Redis glob syntax) and returns
+    /// how many were removed. Uses SCAN so large keyspaces don't block Redis.
+    pub async fn clear_cache(&self, pattern: &str) -> Result<usize, ServiceError> {
+        let mut conn = self.client.get_multiplexed_tokio_connection().await?;
+        let keys: Vec<String> = {
+            let mut iter: redis::AsyncIter<String> = conn.scan_match(pattern).await?;
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+
+        for chunk in keys.chunks(500) {
+            conn.del::<_, ()>(chunk).await?;
+        }
+        Ok(keys.len())
     }
 }
@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// Errors returned by the 7TV, storage and cache services, split so callers
+/// can tell an upstream failure from a parse error or a missing resource.
+#[derive(Debug)]
+pub enum ServiceError {
+    /// The request never got a response (connect, timeout, body read).
+    Http(reqwest::Error),
+    /// 7TV answered with a non-success status, or with GraphQL `errors`.
+    Upstream { status: u16, body: String },
+    /// The response didn't have the shape we expected.
+    Parse(serde_json::Error),
+    /// The storage backend failed or isn't configured.
+    Storage(String),
+    /// Redis failed.
+    Cache(redis::RedisError),
+    NotFound,
+}
+
+impl ServiceError {
+    /// A `Parse` error for a response that is valid JSON but is missing data.
+    pub fn invalid_response(msg: &str) -> Self {
+        ServiceError::Parse(serde::de::Error::custom(format!("Invalid response format: {}", msg)))
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::Http(e) => write!(f, "HTTP request failed: {}", e),
+            ServiceError::Upstream { status, body } => write!(f, "7TV API Error: {} - {}", status, body),
+            ServiceError::Parse(e) => write!(f, "{}", e),
+            ServiceError::Storage(msg) => write!(f, "Storage error: {}", msg),
+            ServiceError::Cache(e) => write!(f, "Cache error: {}", e),
+            ServiceError::NotFound => write!(f, "Not found"),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ServiceError::Http(e) => Some(e),
+            ServiceError::Parse(e) => Some(e),
+            ServiceError::Cache(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ServiceError {
+    fn from(e: reqwest::Error) -> Self {
+        ServiceError::Http(e)
+    }
+}
+
+impl From<serde_json::Error> for ServiceError {
+    fn from(e: serde_json::Error) -> Self {
+        ServiceError::Parse(e)
+    }
+}
+
+impl From<redis::RedisError> for ServiceError {
+    fn from(e: redis::RedisError) -> Self {
+        ServiceError::Cache(e)
+    }
+}
+
+impl From<azure_storage::Error> for ServiceError {
+    fn from(e: azure_storage::Error) -> Self {
+        match e.as_http_error() {
+            Some(http) if u16::from(http.status()) == 404 => ServiceError::NotFound,
+            _ => ServiceError::Storage(e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for ServiceError {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::NotFound => ServiceError::NotFound,
+            _ => ServiceError::Storage(e.to_string()),
+        }
+    }
+}
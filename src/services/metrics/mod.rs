@@ -0,0 +1,88 @@
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+
+/// Prometheus collectors for the service, exposed at `GET /metrics`.
+pub struct Metrics {
+    registry: Registry,
+    cache_requests: IntCounterVec,
+    cache_errors: IntCounter,
+    seventv_requests: IntCounter,
+    seventv_errors: IntCounter,
+    blobs_uploaded: IntCounter,
+    sync_duration: HistogramVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let cache_requests = IntCounterVec::new(
+            Opts::new("cache_requests_total", "Response cache lookups by endpoint and result"),
+            &["endpoint", "result"],
+        )
+        .unwrap();
+        let cache_errors = IntCounter::new("cache_errors_total", "Redis operations that failed").unwrap();
+        let seventv_requests = IntCounter::new("seventv_requests_total", "Requests sent to the 7TV API, including retries").unwrap();
+        let seventv_errors = IntCounter::new("seventv_errors_total", "7TV API requests that failed or returned an error").unwrap();
+        let blobs_uploaded = IntCounter::new("blobs_uploaded_total", "Blobs written to the configured storage backend").unwrap();
+        let sync_duration = HistogramVec::new(
+            HistogramOpts::new("sync_duration_seconds", "Duration of sync runs by kind and outcome")
+                .buckets(vec![1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0]),
+            &["kind", "outcome"],
+        )
+        .unwrap();
+
+        registry.register(Box::new(cache_requests.clone())).unwrap();
+        registry.register(Box::new(cache_errors.clone())).unwrap();
+        registry.register(Box::new(seventv_requests.clone())).unwrap();
+        registry.register(Box::new(seventv_errors.clone())).unwrap();
+        registry.register(Box::new(blobs_uploaded.clone())).unwrap();
+        registry.register(Box::new(sync_duration.clone())).unwrap();
+
+        Self {
+            registry,
+            cache_requests,
+            cache_errors,
+            seventv_requests,
+            seventv_errors,
+            blobs_uploaded,
+            sync_duration,
+        }
+    }
+
+    pub fn record_cache_lookup(&self, endpoint: &str, hit: bool) {
+        let result = if hit { "hit" } else { "miss" };
+        self.cache_requests.with_label_values(&[endpoint, result]).inc();
+    }
+
+    pub fn record_cache_error(&self) {
+        self.cache_errors.inc();
+    }
+
+    pub fn record_seventv_request(&self) {
+        self.seventv_requests.inc();
+    }
+
+    pub fn record_seventv_error(&self) {
+        self.seventv_errors.inc();
+    }
+
+    pub fn record_blob_upload(&self) {
+        self.blobs_uploaded.inc();
+    }
+
+    pub fn observe_sync(&self, kind: &str, success: bool, seconds: f64) {
+        let outcome = if success { "success" } else { "failure" };
+        self.sync_duration.with_label_values(&[kind, outcome]).observe(seconds);
+    }
+
+    /// Renders every collector in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!("Failed to encode metrics: {:?}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
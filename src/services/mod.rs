@@ -1,3 +1,5 @@
 pub mod cache;
+pub mod error;
+pub mod metrics;
 pub mod seventv;
 pub mod storage;
@@ -1,9 +1,19 @@
-use crate::models::{EmoteResponse, TrendingPeriod};
+use crate::config::{Config, FilenameStrategy};
+use crate::models::{AnimationFilter, EmoteResponse, EmoteVariant, EmoteVariantsResponse, ImageFormat, SearchSort, TagMatch, TrendingPeriod};
+use crate::services::error::ServiceError;
+use crate::services::metrics::Metrics;
 use crate::services::storage::StorageService;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use futures::stream::{self, StreamExt};
-use reqwest::header::CONTENT_TYPE;
+use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+mod singleflight;
+use singleflight::SingleFlight;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -16,6 +26,20 @@ pub struct Emote {
     pub host: Option<TrendingHost>,
     pub animated: Option<bool>,
     pub tags: Option<Vec<String>>,
+    pub flags: Option<EmoteFlags>,
+}
+
+impl Emote {
+    pub fn is_zero_width(&self) -> bool {
+        self.flags.as_ref().and_then(|f| f.zero_width).unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EmoteFlags {
+    /// Aliased from 7TV's `defaultZeroWidth` in every query.
+    pub zero_width: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -38,6 +62,9 @@ pub struct Image {
     pub size: i32,
     pub scale: i32,
     pub width: i32,
+    /// Older responses and some variants omit the height.
+    #[serde(default)]
+    pub height: Option<i32>,
     pub frame_count: i32,
 }
 
@@ -57,21 +84,139 @@ pub struct TrendingFile {
     pub height: i32,
 }
 
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub emotes: Vec<Emote>,
+    pub total_count: i32,
+    pub page_count: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmoteFailure {
+    pub emote_id: String,
+    pub reason: String,
+}
+
+/// Outcome of `process_emotes_batch`: stored emotes plus the ones that failed.
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    pub emotes: Vec<EmoteResponse>,
+    pub failures: Vec<EmoteFailure>,
+}
+
+/// Per-request knobs for how emotes are selected and stored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessOptions {
+    /// Largest image scale to store; `None` picks the largest available.
+    pub preferred_scale: Option<i32>,
+    /// Format to pick when the emote has it at the chosen scale range.
+    pub preferred_format: Option<ImageFormat>,
+    /// Re-encode static WebP images as PNG before upload.
+    pub convert_to_png: bool,
+    /// Replace blobs that already exist instead of reusing them. Syncs set
+    /// this so a truncated upload from an earlier run gets fixed.
+    pub overwrite: bool,
+}
+
+/// Deployment-wide rules for picking and naming stored images, from
+/// `IMAGE_FORMAT_PREFERENCE` and `FILENAME_STRATEGY`.
+#[derive(Debug, Clone)]
+pub struct FileRules {
+    /// Formats best-first, used when a request has no `preferred_format`.
+    pub format_preference: Vec<ImageFormat>,
+    pub filename_strategy: FilenameStrategy,
+}
+
+impl Default for FileRules {
+    fn default() -> Self {
+        Self {
+            format_preference: ImageFormat::DEFAULT_PREFERENCE.to_vec(),
+            filename_strategy: FilenameStrategy::default(),
+        }
+    }
+}
+
+impl FileRules {
+    pub fn from_config(cfg: &Config) -> Self {
+        Self {
+            format_preference: cfg.image_format_preference.clone(),
+            filename_strategy: cfg.filename_strategy,
+        }
+    }
+}
+
+/// Restrictions on a search beyond the query text. The default applies none.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    /// Normalized with `normalize_tags`; empty means no tag filter.
+    pub tags: Vec<String>,
+    pub tag_match: TagMatch,
+    /// Only keep emotes whose name equals the query.
+    pub exact_match: bool,
+    /// Compare exact matches case-sensitively.
+    pub case_sensitive: bool,
+    pub sort: SearchSort,
+    /// Drop zero-width overlay emotes from the page.
+    pub exclude_zero_width: bool,
+}
+
+impl SearchFilters {
+    fn name_matches(&self, query: &str, name: Option<&str>) -> bool {
+        match name {
+            Some(name) if self.case_sensitive => name == query,
+            Some(name) => name.to_lowercase() == query.to_lowercase(),
+            None => false,
+        }
+    }
+}
+
+/// A page of search results after its emotes have been stored.
+#[derive(Debug, Clone)]
+pub struct ProcessedSearch {
+    pub total_count: i32,
+    pub page_count: i32,
+    pub batch: BatchResult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchProgress {
+    pub processed: usize,
+    pub total: usize,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmoteSet {
+    pub name: String,
+    pub emotes: Vec<Emote>,
+}
+
 #[derive(Serialize)]
 struct GqlRequest<'a> {
     query: &'a str,
     variables: serde_json::Value,
 }
 
+#[derive(Clone)]
 pub struct SevenTVService {
     client: reqwest::Client,
+    api_url: String,
     storage: Arc<StorageService>,
+    download_concurrency: usize,
+    download_timeout: Duration,
+    metrics: Arc<Metrics>,
+    search_flight: Arc<SingleFlight<Result<ProcessedSearch, ServiceError>>>,
+    trending_flight: Arc<SingleFlight<Result<BatchResult, ServiceError>>>,
+    /// Once cancelled, batches stop starting new emotes.
+    shutdown: CancellationToken,
+    rules: Arc<FileRules>,
 }
 
 impl SevenTVService {
-    pub fn new(storage: Arc<StorageService>) -> Self {
+    pub fn new(cfg: &Config, storage: Arc<StorageService>, metrics: Arc<Metrics>, shutdown: CancellationToken) -> Self {
         let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
+            .timeout(Duration::from_secs(cfg.gql_timeout_secs))
             .pool_idle_timeout(std::time::Duration::from_secs(90))
             .pool_max_idle_per_host(10)
             .build()
@@ -79,7 +224,126 @@ impl SevenTVService {
 
         Self {
             client,
+            api_url: cfg.seventv_api_url.clone(),
             storage,
+            download_concurrency: cfg.emote_download_concurrency,
+            download_timeout: Duration::from_secs(cfg.download_timeout_secs),
+            metrics,
+            search_flight: Arc::new(SingleFlight::new()),
+            trending_flight: Arc::new(SingleFlight::new()),
+            shutdown,
+            rules: Arc::new(FileRules::from_config(cfg)),
+        }
+    }
+
+    /// Rules for the free functions that describe emotes without storing them.
+    pub fn file_rules(&self) -> &FileRules {
+        &self.rules
+    }
+
+    /// Posts a GraphQL query to 7TV, retrying on 429/5xx responses and network
+    /// errors with exponential backoff (250ms, 500ms, 1s). A `Retry-After`
+    /// header on a 429 takes precedence over the computed delay.
+    async fn post_gql(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value, ServiceError> {
+        const MAX_RETRIES: u32 = 3;
+        let mut attempt = 0;
+
+        loop {
+            let backoff = Duration::from_millis(250 * 2u64.pow(attempt));
+
+            self.metrics.record_seventv_request();
+            let resp = match self.client.post(&self.api_url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&GqlRequest { query, variables: variables.clone() })
+                .send()
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    self.metrics.record_seventv_error();
+                    if attempt >= MAX_RETRIES {
+                        return Err(e.into());
+                    }
+                    tracing::warn!("7TV request failed ({}), retrying in {:?}", e, backoff);
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                    continue;
+                }
+            };
+
+            let status = resp.status();
+            tracing::info!("7TV API Response Status: {}", status);
+            if !status.is_success() {
+                self.metrics.record_seventv_error();
+            }
+
+            if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < MAX_RETRIES {
+                let delay = resp.headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .filter(|_| status == StatusCode::TOO_MANY_REQUESTS)
+                    .map(Duration::from_secs)
+                    .unwrap_or(backoff);
+                tracing::warn!("7TV API returned {}, retrying in {:?}", status, delay);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            if !status.is_success() {
+                let error_text = resp.text().await.unwrap_or_default();
+                tracing::error!("7TV API Error Body: {}", error_text);
+                return Err(ServiceError::Upstream { status: status.as_u16(), body: error_text });
+            }
+
+            let body_text = resp.text().await?;
+            let body: serde_json::Value = serde_json::from_str(&body_text)?;
+
+            if let Some(errors) = body.get("errors") {
+                self.metrics.record_seventv_error();
+                tracing::error!("7TV API GraphQL Errors: {:?}", errors);
+                return Err(ServiceError::Upstream { status: status.as_u16(), body: errors.to_string() });
+            }
+
+            return Ok(body);
+        }
+    }
+
+    /// Posts a JSON payload to a caller-supplied webhook with a short timeout,
+    /// retrying once on failure.
+    pub async fn post_webhook<T: Serialize>(
+        &self,
+        url: &str,
+        payload: &T,
+    ) -> Result<(), ServiceError> {
+        const ATTEMPTS: u32 = 2;
+        let mut attempt = 1;
+
+        loop {
+            let result = match self.client.post(url)
+                .timeout(Duration::from_secs(5))
+                .json(payload)
+                .send()
+                .await
+            {
+                Ok(resp) if resp.status().is_success() => return Ok(()),
+                Ok(resp) => Err(ServiceError::Upstream {
+                    status: resp.status().as_u16(),
+                    body: resp.text().await.unwrap_or_default(),
+                }),
+                Err(e) => Err(e.into()),
+            };
+
+            if attempt == ATTEMPTS {
+                return result;
+            }
+            attempt += 1;
+            tokio::time::sleep(Duration::from_millis(500)).await;
         }
     }
 
@@ -88,14 +352,15 @@ impl SevenTVService {
         query: &str,
         page: i32,
         limit: i32,
-        animated_only: bool,
-    ) -> Result<Vec<Emote>, Box<dyn std::error::Error + Send + Sync>> {
+        animation: AnimationFilter,
+        filters: &SearchFilters,
+    ) -> Result<SearchResult, ServiceError> {
         let gql = r#"
-        query EmoteSearch($query: String, $tags: [String!]!, $sortBy: SortBy!, $filters: Filters, $page: Int, $perPage: Int!, $isDefaultSetSet: Boolean!, $defaultSetId: Id!) {
+        query EmoteSearch($query: String, $tags: [String!]!, $tagMatch: TagsMatch!, $sortBy: SortBy!, $filters: Filters, $page: Int, $perPage: Int!, $isDefaultSetSet: Boolean!, $defaultSetId: Id!) {
           emotes {
             search(
               query: $query
-              tags: { tags: $tags, match: ANY }
+              tags: { tags: $tags, match: $tagMatch }
               sort: { sortBy: $sortBy, order: DESCENDING }
               filters: $filters
               page: $page
@@ -115,9 +380,13 @@ impl SevenTVService {
                   size
                   scale
                   width
+                  height
                   frameCount
                 }
                 tags
+                flags {
+                  zeroWidth: defaultZeroWidth
+                }
                 ranking(ranking: TRENDING_WEEKLY)
                 inEmoteSets(emoteSetIds: [$defaultSetId]) @include(if: $isDefaultSetSet) {
                   emoteSetId
@@ -134,60 +403,63 @@ impl SevenTVService {
         }
         "#;
 
+        let mut gql_filters = animation_filters(animation);
+        if filters.exact_match {
+            gql_filters["exactMatch"] = serde_json::Value::Bool(true);
+        }
+
         let variables = serde_json::json!({
             "defaultSetId": "",
-            "filters": { "animated": animated_only },
+            "filters": gql_filters,
             "isDefaultSetSet": false,
             "page": page,
             "perPage": limit,
             "query": query,
-            "sortBy": "TOP_ALL_TIME",
-            "tags": []
+            "sortBy": filters.sort.as_gql(),
+            "tagMatch": filters.tag_match.as_gql(),
+            "tags": filters.tags,
         });
 
-        let resp = self.client.post("https://api.7tv.app/v4/gql")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&GqlRequest { query: gql, variables })
-            .send()
-            .await?;
-
-        let status = resp.status();
-        tracing::info!("7TV Search API Response Status: {}", status);
+        let body = self.post_gql(gql, variables).await?;
 
-        if !status.is_success() {
-            let error_text = resp.text().await.unwrap_or_default();
-            tracing::error!("7TV Search API Error Body: {}", error_text);
-            return Err(format!("7TV API Error: {} - {}", status, error_text).into());
-        }
-
-        let body_text = resp.text().await?;
-        let body: serde_json::Value = serde_json::from_str(&body_text)?;
+        let search = &body["data"]["emotes"]["search"];
+        let items = search["items"]
+            .as_array()
+            .ok_or_else(|| ServiceError::invalid_response("missing data.emotes.search.items"))?;
         
-        if let Some(errors) = body.get("errors") {
-            tracing::error!("7TV Search API GraphQL Errors: {:?}", errors);
-            return Err(format!("7TV GraphQL Error: {}", errors).into());
+        let mut emotes: Vec<Emote> = serde_json::from_value(serde_json::Value::Array(items.clone()))?;
+        // 7TV's exactMatch ignores case; retain() keeps the ranking order
+        if filters.exact_match {
+            emotes.retain(|e| filters.name_matches(query, e.default_name.as_deref()));
         }
+        if filters.exclude_zero_width {
+            emotes.retain(|e| !e.is_zero_width());
+        }
+        let total_count = search["totalCount"].as_i64().unwrap_or(emotes.len() as i64) as i32;
+        let page_count = search["pageCount"].as_i64().unwrap_or(1) as i32;
 
-        let items = body["data"]["emotes"]["search"]["items"]
-            .as_array()
-            .ok_or("Invalid response format: missing data.emotes.search.items")?;
-        
-        let emotes: Vec<Emote> = serde_json::from_value(serde_json::Value::Array(items.clone()))?;
-        Ok(emotes)
+        Ok(SearchResult {
+            emotes,
+            total_count,
+            page_count,
+        })
     }
 
+    /// `tags` narrows the ranking to emotes with any of the given tags; an
+    /// empty slice applies no tag filter.
     pub async fn fetch_trending_emotes(
         &self,
         period: &TrendingPeriod,
         limit: i32,
-        animated_only: bool
-    ) -> Result<Vec<Emote>, Box<dyn std::error::Error + Send + Sync>> {
-        tracing::info!("Fetching trending emotes: period={:?}, limit={}, animated={}", period, limit, animated_only);
+        animation: AnimationFilter,
+        tags: &[String],
+    ) -> Result<Vec<Emote>, ServiceError> {
+        tracing::info!("Fetching trending emotes: period={:?}, limit={}, animation={}, tags={:?}", period, limit, animation.as_str(), tags);
         
         let gql = r#"
-        query GetTrendingEmotes($perPage: Int, $filters: Filters, $sortBy: SortBy!) {
+        query GetTrendingEmotes($perPage: Int, $filters: Filters, $sortBy: SortBy!, $tags: [String!]!) {
             emotes {
-                search(query: "", perPage: $perPage, filters: $filters, sort: { sortBy: $sortBy, order: DESCENDING }) {
+                search(query: "", tags: { tags: $tags, match: ANY }, perPage: $perPage, filters: $filters, sort: { sortBy: $sortBy, order: DESCENDING }) {
                     items {
                         id
                         defaultName
@@ -197,6 +469,7 @@ impl SevenTVService {
                             size
                             scale
                             width
+                            height
                             frameCount
                         }
                         owner {
@@ -205,51 +478,29 @@ impl SevenTVService {
                             }
                         }
                         tags
+                        flags {
+                            zeroWidth: defaultZeroWidth
+                        }
                     }
                 }
             }
         }
         "#;
 
-        let sort_by = match period {
-            TrendingPeriod::Daily => "TRENDING_DAILY",
-            TrendingPeriod::Weekly => "TRENDING_WEEKLY",
-            TrendingPeriod::Monthly => "TRENDING_MONTHLY",
-            TrendingPeriod::AllTime => "TOP_ALL_TIME",
-        };
+        let sort_by = period.sort_by();
 
         let variables = serde_json::json!({
             "perPage": limit,
-            "filters": { "animated": animated_only },
+            "filters": animation_filters(animation),
             "sortBy": sort_by,
+            "tags": tags,
         });
 
-        let resp = self.client.post("https://api.7tv.app/v4/gql")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&GqlRequest { query: gql, variables })
-            .send()
-            .await?;
-
-        let status = resp.status();
-        tracing::info!("7TV API Response Status: {}", status);
-
-        if !status.is_success() {
-            let error_text = resp.text().await.unwrap_or_default();
-            tracing::error!("7TV API Error Body: {}", error_text);
-            return Err(format!("7TV API Error: {} - {}", status, error_text).into());
-        }
-
-        let body_text = resp.text().await?;
-        let body: serde_json::Value = serde_json::from_str(&body_text)?;
-
-        if let Some(errors) = body.get("errors") {
-            tracing::error!("7TV Trending API GraphQL Errors: {:?}", errors);
-            return Err(format!("7TV GraphQL Error: {}", errors).into());
-        }
+        let body = self.post_gql(gql, variables).await?;
 
         let items = body["data"]["emotes"]["search"]["items"]
             .as_array()
-            .ok_or("Invalid response format: missing data.emotes.search.items")?;
+            .ok_or_else(|| ServiceError::invalid_response("missing data.emotes.search.items"))?;
 
         let emotes: Vec<Emote> = serde_json::from_value(serde_json::Value::Array(items.clone()))?;
         Ok(emotes)
@@ -259,7 +510,7 @@ impl SevenTVService {
         &self,
         user_id: &str,
         limit: i32,
-    ) -> Result<Vec<Emote>, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<Vec<Emote>, ServiceError> {
         tracing::info!("Fetching user emotes: user_id={}, limit={}", user_id, limit);
 
 
@@ -285,9 +536,13 @@ impl SevenTVService {
                             size
                             scale
                             width
+                            height
                             frameCount
                         }
                         tags
+                        flags {
+                          zeroWidth: defaultZeroWidth
+                        }
                       }
                     }
                   }
@@ -303,31 +558,12 @@ impl SevenTVService {
             "perPage": limit,
         });
 
-        let resp = self.client.post("https://api.7tv.app/v4/gql")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&GqlRequest { query: gql, variables })
-            .send()
-            .await?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            let error_text = resp.text().await.unwrap_or_default();
-            return Err(format!("7TV API Error: {} - {}", status, error_text).into());
-        }
-
-        let body_text = resp.text().await?;
-        let body: serde_json::Value = serde_json::from_str(&body_text)?;
-        
-        if let Some(errors) = body.get("errors") {
-            tracing::error!("7TV User Emotes API GraphQL Errors: {:?}", errors);
-            return Err(format!("7TV GraphQL Error: {}", errors).into());
-        }
+        let body = self.post_gql(gql, variables).await?;
 
-        
         // Traverse path: data.users.user.style.activeEmoteSet.emotes.items
         let items_wrapper = body["data"]["users"]["user"]["style"]["activeEmoteSet"]["emotes"]["items"]
             .as_array()
-            .ok_or("Invalid response format: missing emotes list")?;
+            .ok_or_else(|| ServiceError::invalid_response("missing emotes list"))?;
 
         // Extract "emote" field from each item to get the actual emote data
         let emotes_json: Vec<serde_json::Value> = items_wrapper.iter()
@@ -338,107 +574,658 @@ impl SevenTVService {
         Ok(emotes)
     }
 
+    /// Resolves a platform login (e.g. a Twitch username) to a 7TV user ID.
+    /// Returns `Ok(None)` when no 7TV user is linked to that connection.
+    pub async fn resolve_user_by_connection(
+        &self,
+        platform: &str,
+        username: &str,
+    ) -> Result<Option<String>, ServiceError> {
+        tracing::info!("Resolving 7TV user: platform={}, username={}", platform, username);
+
+        let gql = r#"
+        query UserByConnection($platform: Platform!, $username: String!) {
+          users {
+            userByConnection(platform: $platform, platformUsername: $username) {
+              id
+            }
+          }
+        }
+        "#;
+
+        let variables = serde_json::json!({
+            "platform": platform.to_uppercase(),
+            "username": username,
+        });
+
+        let body = self.post_gql(gql, variables).await?;
+
+        Ok(body["data"]["users"]["userByConnection"]["id"]
+            .as_str()
+            .map(|id| id.to_string()))
+    }
+
+    pub async fn fetch_emote_set(
+        &self,
+        set_id: &str,
+        limit: i32,
+    ) -> Result<Option<EmoteSet>, ServiceError> {
+        tracing::info!("Fetching emote set: set_id={}, limit={}", set_id, limit);
+
+        let gql = r#"
+        query GetEmoteSet($setId: Id!, $perPage: Int!) {
+          emoteSets {
+            emoteSet(id: $setId) {
+              name
+              emotes(page: 1, perPage: $perPage) {
+                items {
+                  emote {
+                    id
+                    defaultName
+                    owner {
+                      mainConnection {
+                        platformDisplayName
+                      }
+                    }
+                    images {
+                        url
+                        mime
+                        size
+                        scale
+                        width
+                        height
+                        frameCount
+                    }
+                    tags
+                    flags {
+                      zeroWidth: defaultZeroWidth
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+        "#;
+
+        let variables = serde_json::json!({
+            "setId": set_id,
+            "perPage": limit,
+        });
+
+        let body = self.post_gql(gql, variables).await?;
+
+        let set_json = &body["data"]["emoteSets"]["emoteSet"];
+        if set_json.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(EmoteSet {
+            name: set_json["name"].as_str().unwrap_or("Unknown").to_string(),
+            emotes: set_emotes(set_json)?,
+        }))
+    }
+
+    /// Fetches the 7TV global emote set, the baseline every chat client shows.
+    pub async fn fetch_global_emotes(&self, limit: i32) -> Result<Vec<Emote>, ServiceError> {
+        tracing::info!("Fetching global emotes: limit={}", limit);
+
+        let gql = r#"
+        query GetGlobalEmotes($perPage: Int!) {
+          emoteSets {
+            global {
+              emotes(page: 1, perPage: $perPage) {
+                items {
+                  emote {
+                    id
+                    defaultName
+                    owner {
+                      mainConnection {
+                        platformDisplayName
+                      }
+                    }
+                    images {
+                        url
+                        mime
+                        size
+                        scale
+                        width
+                        height
+                        frameCount
+                    }
+                    tags
+                    flags {
+                      zeroWidth: defaultZeroWidth
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+        "#;
+
+        let variables = serde_json::json!({
+            "perPage": limit,
+        });
+
+        let body = self.post_gql(gql, variables).await?;
+
+        let set_json = &body["data"]["emoteSets"]["global"];
+        if set_json.is_null() {
+            return Err(ServiceError::invalid_response("missing global emote set"));
+        }
+        set_emotes(set_json)
+    }
+
+    pub async fn fetch_emote_by_id(
+        &self,
+        emote_id: &str,
+    ) -> Result<Option<Emote>, ServiceError> {
+        tracing::info!("Fetching emote by id: {}", emote_id);
+
+        let gql = r#"
+        query GetEmote($id: Id!) {
+          emotes {
+            emote(id: $id) {
+              id
+              defaultName
+              owner {
+                mainConnection {
+                  platformDisplayName
+                }
+              }
+              images {
+                url
+                mime
+                size
+                scale
+                width
+                height
+                frameCount
+              }
+              tags
+              flags {
+                zeroWidth: defaultZeroWidth
+              }
+            }
+          }
+        }
+        "#;
+
+        let variables = serde_json::json!({
+            "id": emote_id,
+        });
+
+        let body = self.post_gql(gql, variables).await?;
+
+        // 7TV returns `emote: null` for unknown IDs
+        let emote_json = &body["data"]["emotes"]["emote"];
+        if emote_json.is_null() {
+            return Ok(None);
+        }
+
+        let emote: Emote = serde_json::from_value(emote_json.clone())?;
+        Ok(Some(emote))
+    }
+
+    pub async fn process_emote(
+        &self,
+        emote: Emote,
+        folder: &str,
+        options: ProcessOptions,
+    ) -> Result<EmoteResponse, String> {
+        process_single_emote(
+            self.client.clone(), self.download_timeout, emote, Arc::clone(&self.storage), folder, options, &self.rules
+        ).await
+    }
+
+    /// Searches 7TV and stores the resulting page into `folder`. Concurrent
+    /// calls with the same `key` share one upstream search and one batch.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_and_process(
+        &self,
+        key: &str,
+        query: &str,
+        page: i32,
+        limit: i32,
+        animation: AnimationFilter,
+        filters: SearchFilters,
+        folder: &str,
+        options: ProcessOptions,
+    ) -> Arc<Result<ProcessedSearch, ServiceError>> {
+        let this = self.clone();
+        let query = query.to_string();
+        let folder = folder.to_string();
+        self.search_flight.run(key, async move {
+            let result = this.search_emotes(&query, page, limit, animation, &filters).await?;
+            let batch = this.process_emotes_batch(result.emotes, &folder, options).await;
+            Ok(ProcessedSearch {
+                total_count: result.total_count,
+                page_count: result.page_count,
+                batch,
+            })
+        }).await
+    }
+
+    /// Fetches trending emotes and stores them into `folder`. Concurrent
+    /// calls with the same `key` share one upstream fetch and one batch.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn trending_and_process(
+        &self,
+        key: &str,
+        period: TrendingPeriod,
+        limit: i32,
+        animation: AnimationFilter,
+        tags: Vec<String>,
+        folder: &str,
+        options: ProcessOptions,
+    ) -> Arc<Result<BatchResult, ServiceError>> {
+        let this = self.clone();
+        let folder = folder.to_string();
+        self.trending_flight.run(key, async move {
+            let emotes = this.fetch_trending_emotes(&period, limit, animation, &tags).await?;
+            Ok(this.process_emotes_batch(emotes, &folder, options).await)
+        }).await
+    }
+
     pub async fn process_emotes_batch(
         &self,
         emotes: Vec<Emote>,
         folder: &str,
-    ) -> Vec<EmoteResponse> {
+        options: ProcessOptions,
+    ) -> BatchResult {
+        self.process_emotes_batch_with_progress(emotes, folder, options, None).await
+    }
+
+    /// Same as `process_emotes_batch`, but reports each finished emote on
+    /// `progress`. Send failures (e.g. a dropped receiver) are ignored so the
+    /// batch always runs to completion. After shutdown starts, emotes not yet
+    /// started are reported as failures instead of being downloaded.
+    pub async fn process_emotes_batch_with_progress(
+        &self,
+        emotes: Vec<Emote>,
+        folder: &str,
+        options: ProcessOptions,
+        progress: Option<mpsc::Sender<BatchProgress>>,
+    ) -> BatchResult {
         let storage = Arc::clone(&self.storage);
         let folder = folder.to_string();
+        let total = emotes.len();
         
-        stream::iter(emotes)
-            .map(|e| {
+        // Tag each emote with its input position so the output keeps 7TV's ranking
+        let mut results = stream::iter(emotes.into_iter().enumerate())
+            .map(|(index, e)| {
                 let storage = Arc::clone(&storage);
                 let folder = folder.clone();
                 let client = self.client.clone();
+                let timeout = self.download_timeout;
+                let shutdown = self.shutdown.clone();
+                let rules = Arc::clone(&self.rules);
                 async move {
-                    process_single_emote(client, e, storage, &folder).await
+                    let emote_id = e.id.clone();
+                    let name = e.default_name.clone().or_else(|| e.name.clone()).unwrap_or_default();
+                    if shutdown.is_cancelled() {
+                        return (index, emote_id, name, Err("skipped: server is shutting down".to_string()));
+                    }
+                    (index, emote_id, name, process_single_emote(client, timeout, e, storage, &folder, options, &rules).await)
                 }
             })
-            .buffer_unordered(5) // Reduced concurrency to prevent timeouts
-            .filter_map(|res| async move { res })
-            .collect()
-            .await
+            .buffer_unordered(self.download_concurrency);
+
+        let mut emotes = Vec::with_capacity(total);
+        let mut failures = Vec::new();
+        while let Some((index, emote_id, name, result)) = results.next().await {
+            match result {
+                Ok(emote) => emotes.push((index, emote)),
+                Err(reason) => {
+                    tracing::warn!("Failed to process emote {}: {}", emote_id, reason);
+                    failures.push((index, EmoteFailure { emote_id, reason }));
+                }
+            }
+
+            if let Some(tx) = &progress {
+                let _ = tx.send(BatchProgress {
+                    processed: emotes.len() + failures.len(),
+                    total,
+                    name,
+                }).await;
+            }
+        }
+
+        emotes.sort_unstable_by_key(|(index, _)| *index);
+        failures.sort_unstable_by_key(|(index, _)| *index);
+        BatchResult {
+            emotes: emotes.into_iter().map(|(_, emote)| emote).collect(),
+            failures: failures.into_iter().map(|(_, failure)| failure).collect(),
+        }
+    }
+}
+
+/// GQL `Filters` for an animation filter; `any` sends no `animated` key.
+fn animation_filters(animation: AnimationFilter) -> serde_json::Value {
+    match animation.gql_animated() {
+        Some(animated) => serde_json::json!({ "animated": animated }),
+        None => serde_json::json!({}),
     }
 }
 
+/// Pulls the emotes out of an emote set's `emotes.items[].emote` list.
+fn set_emotes(set_json: &serde_json::Value) -> Result<Vec<Emote>, ServiceError> {
+    let items_wrapper = set_json["emotes"]["items"]
+        .as_array()
+        .ok_or_else(|| ServiceError::invalid_response("missing emotes list"))?;
+
+    let emotes_json: Vec<serde_json::Value> = items_wrapper.iter()
+        .filter_map(|item| item.get("emote").cloned())
+        .collect();
+
+    Ok(serde_json::from_value(serde_json::Value::Array(emotes_json))?)
+}
+
 async fn process_single_emote(
     client: reqwest::Client,
+    timeout: Duration,
     e: Emote,
     storage: Arc<StorageService>,
     folder: &str,
-) -> Option<EmoteResponse> {
-    let images = if let Some(imgs) = &e.images {
-        imgs.clone()
-    } else if let Some(host) = &e.host {
-        // Construct images from host files if images array is missing (trending endpoint)
-        let animated = e.animated.unwrap_or(false);
-        host.files.iter().map(|f| {
-            let scale_str = f.name.trim_end_matches(&format!("x.{}", f.format)); // simplistic parsing
-            let scale = scale_str.parse().unwrap_or(1);
-            let mime = format!("image/{}", f.format);
-            let url = format!("https:{}/{}", host.url, f.name);
-            Image {
-                url,
-                mime,
-                size: 0,
-                scale,
-                width: f.width,
-                frame_count: if animated { 2 } else { 1 },
-            }
-        }).collect()
-    } else {
-        return None;
-    };
+    options: ProcessOptions,
+    rules: &FileRules,
+) -> Result<EmoteResponse, String> {
+    let images = emote_images(&e)?;
+    let best_image = select_best_image(&images, options, &rules.format_preference).ok_or("no usable image variant")?;
 
-    let best_image = select_best_image(&images)?;
-
-    let resp = client.get(&best_image.url).send().await.ok()?;
+    let resp = client.get(&best_image.url).timeout(timeout).send().await
+        .map_err(|e| format!("download failed: {}", e))?;
     if !resp.status().is_success() {
-        return None;
+        return Err(format!("download failed with status {}", resp.status()));
     }
-    let data = resp.bytes().await.ok()?.to_vec();
+    let content_length = resp.content_length();
+    let mut data = resp.bytes().await
+        .map_err(|e| format!("download failed: {}", e))?
+        .to_vec();
+    verify_download_size(data.len(), content_length, best_image.size)?;
 
-    let extension = match best_image.mime.as_str() {
-        "image/webp" => ".webp",
-        "image/gif" => ".gif",
-        "image/avif" => ".avif",
-        _ => ".png",
-    };
+    let animated = best_image.frame_count > 1;
+    let mut mime = best_image.mime.clone();
+    let (width, height) = image_dimensions(best_image, &images);
 
-    let name = e.default_name.as_deref().or(e.name.as_deref())?;
-    // sanitize name
-    let safe_name: String = name.chars()
-        .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' || c == ' ' { c } else { '_' })
-        .collect();
-    
-    // Append ID to prevent filename collisions (e.g. multiple "lol" emotes overwriting each other)
-    let file_name = format!("{}_{}{}", safe_name, e.id, extension);
+    // Animated WebP would need APNG output, so only static images are converted
+    if options.convert_to_png && !animated && mime == "image/webp" {
+        data = tokio::task::spawn_blocking(move || webp_to_png(&data))
+            .await
+            .map_err(|e| format!("png conversion failed: {}", e))??;
+        mime = "image/png".to_string();
+    }
+
+    let size_bytes = data.len() as i32;
+    let name = e.default_name.as_deref().or(e.name.as_deref()).ok_or("emote has no name")?;
+    let file_name = emote_file_name(name, &e.id, &mime, rules.filename_strategy);
+    let zero_width = e.is_zero_width();
+    let page_url = EmoteResponse::seventv_page_url(&e.id);
     let blob_name = format!("{}/{}", folder, file_name);
 
-    let url = storage.upload_blob(data, &blob_name, &best_image.mime).await.ok()?;
+    let url = storage.upload_blob(data, &blob_name, &mime, options.overwrite).await
+        .map_err(|e| format!("upload failed: {}", e))?;
 
-    Some(EmoteResponse {
+    Ok(EmoteResponse {
         file_name,
         url,
         emote_id: e.id,
         emote_name: name.to_string(),
         owner: e.owner.and_then(|o| o.main_connection.map(|c| c.platform_display_name)),
+        animated: Some(animated),
+        scale: Some(best_image.scale),
+        mime: Some(mime),
+        tags: e.tags.clone(),
+        width,
+        height,
+        zero_width: Some(zero_width),
+        page_url: Some(page_url),
+        size_bytes: Some(size_bytes),
+    })
+}
+
+/// Rejects a download whose length disagrees with the `Content-Length` header
+/// or the size 7TV reported, so a truncated body is never stored. A zero
+/// `expected_size` (e.g. host.files fallbacks) skips the 7TV check.
+fn verify_download_size(len: usize, content_length: Option<u64>, expected_size: i32) -> Result<(), String> {
+    if let Some(content_length) = content_length {
+        if len as u64 != content_length {
+            return Err(format!("truncated download: got {} of {} bytes", len, content_length));
+        }
+    }
+    if expected_size > 0 && len != expected_size as usize {
+        return Err(format!("download size mismatch: got {} bytes, 7TV reported {}", len, expected_size));
+    }
+    Ok(())
+}
+
+/// Builds an `EmoteResponse` pointing at the best image on the 7TV CDN,
+/// without downloading or storing anything.
+pub fn describe_emote(e: Emote, options: ProcessOptions, rules: &FileRules) -> Result<EmoteResponse, String> {
+    let images = emote_images(&e)?;
+    let best_image = select_best_image(&images, options, &rules.format_preference).ok_or("no usable image variant")?;
+    let name = e.default_name.as_deref().or(e.name.as_deref()).ok_or("emote has no name")?;
+    let (width, height) = image_dimensions(best_image, &images);
+    let zero_width = e.is_zero_width();
+    let page_url = EmoteResponse::seventv_page_url(&e.id);
+
+    Ok(EmoteResponse {
+        file_name: emote_file_name(name, &e.id, &best_image.mime, rules.filename_strategy),
+        url: best_image.url.clone(),
+        emote_id: e.id.clone(),
+        emote_name: name.to_string(),
+        owner: e.owner.and_then(|o| o.main_connection.map(|c| c.platform_display_name)),
         animated: Some(best_image.frame_count > 1),
         scale: Some(best_image.scale),
         mime: Some(best_image.mime.clone()),
         tags: e.tags.clone(),
+        width,
+        height,
+        zero_width: Some(zero_width),
+        page_url: Some(page_url),
+        // host.files fallbacks carry no size
+        size_bytes: Some(best_image.size).filter(|&size| size > 0),
+    })
+}
+
+/// Trims, lowercases and dedups tag filters so equivalent requests share a
+/// cache key. 7TV tags are free-form lowercase words set by emote owners
+/// (e.g. `pepe`, `cat`) and match exactly; unknown tags simply match nothing.
+pub fn normalize_tags(tags: Option<Vec<String>>) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Lists every image variant of an emote along with the one
+/// `select_best_image` picks under default options.
+pub fn describe_variants(e: Emote, rules: &FileRules) -> Result<EmoteVariantsResponse, String> {
+    let images = emote_images(&e)?;
+    let name = e.default_name.as_deref().or(e.name.as_deref()).ok_or("emote has no name")?;
+    let selected_index = select_best_image(&images, ProcessOptions::default(), &rules.format_preference)
+        .and_then(|best| images.iter().position(|i| std::ptr::eq(i, best)));
+
+    Ok(EmoteVariantsResponse {
+        emote_id: e.id.clone(),
+        emote_name: name.to_string(),
+        owner: e.owner.and_then(|o| o.main_connection.map(|c| c.platform_display_name)),
+        tags: e.tags.clone(),
+        variants: images.iter().map(|i| EmoteVariant {
+            url: i.url.clone(),
+            mime: i.mime.clone(),
+            scale: i.scale,
+            width: i.width,
+            height: i.height,
+            frame_count: i.frame_count,
+        }).collect(),
+        selected_index,
     })
 }
 
-fn select_best_image(images: &[Image]) -> Option<&Image> {
+/// Describes emotes for a dry run: names, ids and image metadata are filled
+/// in but `url` and `file_name` are left empty since nothing is stored.
+pub fn preview_emotes(emotes: Vec<Emote>, options: ProcessOptions, rules: &FileRules) -> BatchResult {
+    let mut batch = BatchResult { emotes: Vec::with_capacity(emotes.len()), failures: Vec::new() };
+    for emote in emotes {
+        let emote_id = emote.id.clone();
+        match describe_emote(emote, options, rules) {
+            Ok(described) => batch.emotes.push(EmoteResponse {
+                file_name: String::new(),
+                url: String::new(),
+                ..described
+            }),
+            Err(reason) => batch.failures.push(EmoteFailure { emote_id, reason }),
+        }
+    }
+    batch
+}
+
+/// The emote's image variants, built from `host.files` when 7TV didn't
+/// return an `images` array (trending endpoint).
+fn emote_images(e: &Emote) -> Result<Vec<Image>, String> {
+    if let Some(imgs) = &e.images {
+        return Ok(imgs.clone());
+    }
+
+    let host = e.host.as_ref().ok_or("no images available")?;
+    let animated = e.animated.unwrap_or(false);
+    Ok(host.files.iter().map(|f| {
+        let scale_str = f.name.trim_end_matches(&format!("x.{}", f.format)); // simplistic parsing
+        let scale = scale_str.parse().unwrap_or(1);
+        let mime = format!("image/{}", f.format);
+        let url = host_file_url(&host.url, &f.name);
+        Image {
+            url,
+            mime,
+            size: 0,
+            scale,
+            width: f.width,
+            height: Some(f.height),
+            frame_count: if animated { 2 } else { 1 },
+        }
+    }).collect())
+}
+
+/// Joins a `host.url` and file name. 7TV sends protocol-relative hosts
+/// (`//cdn.7tv.app/emote/ID`), but full URLs and bare hosts are accepted too.
+fn host_file_url(host_url: &str, file_name: &str) -> String {
+    let host_url = host_url.trim_end_matches('/');
+    if host_url.starts_with("http://") || host_url.starts_with("https://") {
+        format!("{}/{}", host_url, file_name)
+    } else if host_url.starts_with("//") {
+        format!("https:{}/{}", host_url, file_name)
+    } else {
+        format!("https://{}/{}", host_url, file_name)
+    }
+}
+
+/// Width and height of `image`. When 7TV omits the height it is derived from
+/// the aspect ratio of another variant that has both.
+fn image_dimensions(image: &Image, images: &[Image]) -> (Option<i32>, Option<i32>) {
+    let width = Some(image.width).filter(|w| *w > 0);
+    let height = image.height.filter(|h| *h > 0).or_else(|| {
+        let width = width?;
+        images
+            .iter()
+            .find_map(|i| i.height.filter(|h| *h > 0 && i.width > 0).map(|h| (i.width, h)))
+            .map(|(w, h)| ((width as f64) * (h as f64) / (w as f64)).round() as i32)
+    });
+    (width, height)
+}
+
+/// `{sanitized name}_{id}{ext}`; the ID suffix prevents collisions between
+/// emotes sharing a name (e.g. multiple "lol" emotes overwriting each other).
+fn emote_file_name(name: &str, emote_id: &str, mime: &str, strategy: FilenameStrategy) -> String {
+    let extension = match mime {
+        "image/webp" => ".webp",
+        "image/gif" => ".gif",
+        "image/avif" => ".avif",
+        _ => ".png",
+    };
+
+    let safe_name = sanitize_name(name, strategy);
+    if safe_name.is_empty() {
+        return format!("{}{}", emote_id, extension);
+    }
+    format!("{}_{}{}", safe_name, emote_id, extension)
+}
+
+/// Cleans an emote name for use in a file name. Returns an empty string when
+/// nothing alphanumeric survives, so callers can fall back to the emote ID.
+fn sanitize_name(name: &str, strategy: FilenameStrategy) -> String {
+    let keep = |c: char| matches!(c, '.' | '-' | '_' | ' ');
+    let cleaned: String = match strategy {
+        FilenameStrategy::Ascii => name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || keep(c) { c } else { '_' })
+            .collect(),
+        FilenameStrategy::PreserveUnicode => name.chars()
+            .map(|c| if c.is_alphanumeric() || keep(c) { c } else { '_' })
+            .collect(),
+        FilenameStrategy::Slug => name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect::<Vec<_>>()
+            .join("-"),
+    };
+
+    if cleaned.chars().any(char::is_alphanumeric) {
+        cleaned
+    } else {
+        String::new()
+    }
+}
+
+/// Decodes a static WebP image and re-encodes it as PNG. This is CPU-bound
+/// (a few milliseconds per 4x emote) and PNGs are typically 2-4x larger than
+/// the WebP source, so it is opt-in per sync.
+fn webp_to_png(data: &[u8]) -> Result<Vec<u8>, String> {
+    let img = image::load_from_memory_with_format(data, image::ImageFormat::WebP)
+        .map_err(|e| format!("webp decode failed: {}", e))?;
+    let mut out = std::io::Cursor::new(Vec::new());
+    img.write_to(&mut out, image::ImageFormat::Png)
+        .map_err(|e| format!("png encode failed: {}", e))?;
+    Ok(out.into_inner())
+}
+
+/// Picks the image to store. With a `preferred_scale`, only variants at or
+/// below that scale are considered (falling back to all variants when none
+/// are small enough), so the largest matching scale wins. A `preferred_format`
+/// narrows that down further when the emote has the format; otherwise
+/// `format_preference` (best first) decides.
+fn select_best_image<'a>(
+    images: &'a [Image],
+    options: ProcessOptions,
+    format_preference: &[ImageFormat],
+) -> Option<&'a Image> {
     if images.is_empty() { return None; }
+
+    let mut candidates: Vec<&Image> = match options.preferred_scale {
+        Some(max_scale) if images.iter().any(|i| i.scale <= max_scale) => {
+            images.iter().filter(|i| i.scale <= max_scale).collect()
+        }
+        _ => images.iter().collect(),
+    };
+
+    if let Some(format) = options.preferred_format {
+        if candidates.iter().any(|i| i.mime == format.mime()) {
+            candidates.retain(|i| i.mime == format.mime());
+        }
+    }
     
     // Sort by checking if animated first, then mime preference, then scale
     // This is a simplified logic compared to Go but sufficient
-    let preferred_mimes = ["image/webp", "image/gif", "image/avif", "image/png"];
+    let preferred_mimes: Vec<&str> = format_preference.iter().map(|f| f.mime()).collect();
     
-    images.iter().max_by(|a, b| {
+    candidates.into_iter().max_by(|a, b| {
         let a_anim = a.frame_count > 1;
         let b_anim = b.frame_count > 1;
         if a_anim != b_anim {
@@ -457,3 +1244,62 @@ fn select_best_image(images: &[Image]) -> Option<&Image> {
         a.scale.cmp(&b.scale)
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_file_url_keeps_full_urls() {
+        assert_eq!(
+            host_file_url("https://cdn.7tv.app/emote/ID", "4x.webp"),
+            "https://cdn.7tv.app/emote/ID/4x.webp"
+        );
+        assert_eq!(
+            host_file_url("http://localhost:9000/emote/ID/", "1x.gif"),
+            "http://localhost:9000/emote/ID/1x.gif"
+        );
+    }
+
+    #[test]
+    fn host_file_url_adds_scheme_to_protocol_relative_hosts() {
+        assert_eq!(
+            host_file_url("//cdn.7tv.app/emote/ID", "4x.webp"),
+            "https://cdn.7tv.app/emote/ID/4x.webp"
+        );
+    }
+
+    #[test]
+    fn host_file_url_adds_scheme_to_bare_hosts() {
+        assert_eq!(
+            host_file_url("cdn.7tv.app/emote/ID", "4x.webp"),
+            "https://cdn.7tv.app/emote/ID/4x.webp"
+        );
+    }
+
+    #[test]
+    fn slug_collapses_repeated_separators() {
+        assert_eq!(sanitize_name("Pepe  --Laugh__!!", FilenameStrategy::Slug), "pepe-laugh");
+        assert_eq!(sanitize_name("  monka S ", FilenameStrategy::Slug), "monka-s");
+    }
+
+    #[test]
+    fn unicode_is_kept_or_replaced_by_strategy() {
+        assert_eq!(sanitize_name("ñandú", FilenameStrategy::PreserveUnicode), "ñandú");
+        assert_eq!(sanitize_name("ñandú", FilenameStrategy::Ascii), "_and_");
+    }
+
+    #[test]
+    fn punctuation_only_names_sanitize_to_empty() {
+        for strategy in [FilenameStrategy::Ascii, FilenameStrategy::PreserveUnicode, FilenameStrategy::Slug] {
+            assert_eq!(sanitize_name("?!?", strategy), "", "{:?}", strategy);
+        }
+    }
+
+    #[test]
+    fn emote_file_name_falls_back_to_id() {
+        assert_eq!(emote_file_name("?!?", "01ABC", "image/webp", FilenameStrategy::Ascii), "01ABC.webp");
+        assert_eq!(emote_file_name("...", "01ABC", "image/gif", FilenameStrategy::Slug), "01ABC.gif");
+        assert_eq!(emote_file_name("lol", "01ABC", "image/png", FilenameStrategy::Ascii), "lol_01ABC.png");
+    }
+}
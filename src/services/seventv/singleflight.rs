@@ -0,0 +1,49 @@
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+/// Coalesces concurrent calls that share a key: the first caller runs the
+/// work and everyone who arrives while it is in flight awaits the same result.
+/// Once the call settles the key is forgotten, so later calls start fresh.
+pub struct SingleFlight<T> {
+    inflight: Mutex<HashMap<String, Shared<BoxFuture<'static, Arc<T>>>>>,
+}
+
+impl<T: Send + Sync + 'static> SingleFlight<T> {
+    pub fn new() -> Self {
+        Self {
+            inflight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn run<F>(&self, key: &str, work: F) -> Arc<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let call = {
+            let mut inflight = self.inflight.lock().unwrap();
+            match inflight.get(key) {
+                Some(call) => {
+                    tracing::debug!("Joining in-flight 7TV call for {}", key);
+                    call.clone()
+                }
+                None => {
+                    let call = work.map(Arc::new).boxed().shared();
+                    inflight.insert(key.to_string(), call.clone());
+                    call
+                }
+            }
+        };
+
+        let result = call.clone().await;
+
+        // Only drop our own entry; a newer call may already own the key
+        let mut inflight = self.inflight.lock().unwrap();
+        if inflight.get(key).is_some_and(|current| current.ptr_eq(&call)) {
+            inflight.remove(key);
+        }
+
+        result
+    }
+}
@@ -0,0 +1,285 @@
+use async_trait::async_trait;
+use azure_storage::StorageCredentials;
+use azure_storage_blobs::blob::CopyStatus;
+use azure_storage_blobs::prelude::*;
+use futures::stream::{self, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::config::Config;
+use crate::services::error::ServiceError;
+use crate::services::metrics::Metrics;
+use super::{BlobInfo, BlobStream, StorageBackend};
+
+/// Azure Blob Storage, configured from `AZURE_CONNECTION_STRING`.
+pub struct AzureBackend {
+    client: Arc<BlobServiceClient>,
+    container_name: String,
+    account_name: String,
+    metrics: Arc<Metrics>,
+}
+
+impl AzureBackend {
+    /// `None` when the connection string is empty or has no usable credentials.
+    pub fn new(cfg: &Config, metrics: Arc<Metrics>) -> Option<Self> {
+        if cfg.azure_conn_str.is_empty() {
+            return None;
+        }
+
+        // Simplistic connection string parsing for demo purposes
+        let field = |name: &str| {
+            cfg.azure_conn_str
+                .split(';')
+                .find_map(|s| s.trim().strip_prefix(name))
+                .map(str::to_string)
+                .filter(|v| !v.is_empty())
+        };
+
+        // SAS connection strings often only carry the endpoint, not the account name
+        let account_name = field("AccountName=")
+            .or_else(|| {
+                field("BlobEndpoint=").and_then(|endpoint| {
+                    endpoint
+                        .trim_start_matches("https://")
+                        .split('.')
+                        .next()
+                        .map(str::to_string)
+                })
+            })
+            .unwrap_or_default();
+
+        // Prefer the account key when both are given; a SAS token is the least-privilege fallback
+        let credentials = match (field("AccountKey="), field("SharedAccessSignature=")) {
+            (Some(account_key), _) if !account_name.is_empty() => {
+                Some(StorageCredentials::access_key(account_name.clone(), account_key))
+            }
+            (_, Some(sas)) if !account_name.is_empty() => match StorageCredentials::sas_token(sas) {
+                Ok(credentials) => Some(credentials),
+                Err(e) => {
+                    tracing::error!("Invalid SharedAccessSignature in Azure connection string: {}", e);
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        let client = BlobServiceClient::new(account_name.clone(), credentials?);
+
+        Some(Self {
+            client: Arc::new(client),
+            container_name: cfg.container_name.clone(),
+            account_name,
+            metrics,
+        })
+    }
+}
+
+#[async_trait]
+impl StorageBackend for AzureBackend {
+    fn name(&self) -> &'static str {
+        "azure"
+    }
+
+    fn get_container_url(&self) -> String {
+        format!("https://{}.blob.core.windows.net/{}", self.account_name, self.container_name)
+    }
+
+    /// Cheap connectivity probe: fetches the container's properties.
+    async fn check_container(&self) -> Result<(), ServiceError> {
+        self.client
+            .container_client(&self.container_name)
+            .get_properties()
+            .into_future()
+            .await?;
+        Ok(())
+    }
+
+    async fn upload_blob(
+        &self,
+        data: Vec<u8>,
+        blob_name: &str,
+        content_type: &str,
+        overwrite: bool,
+    ) -> Result<String, ServiceError> {
+        let container_client = self.client.container_client(&self.container_name);
+        let blob_client = container_client.blob_client(blob_name);
+
+        // Check if exists
+        // Any error is assumed to mean "not found"
+        if !overwrite && blob_client.get_properties().into_future().await.is_ok() {
+            return Ok(format!("https://{}.blob.core.windows.net/{}/{}", 
+                self.account_name, self.container_name, blob_name));
+        }
+
+        blob_client
+            .put_block_blob(data)
+            .content_type(content_type.to_string())
+            .into_future()
+            .await?;
+        self.metrics.record_blob_upload();
+
+        Ok(format!("https://{}.blob.core.windows.net/{}/{}", 
+            self.account_name, self.container_name, blob_name))
+    }
+
+    async fn delete_blobs_by_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<usize, ServiceError> {
+        let container_client = self.client.container_client(&self.container_name);
+
+        let mut stream = container_client
+            .list_blobs()
+            .prefix(prefix.to_string())
+            .into_stream();
+
+        let mut deleted = 0;
+        while let Some(value) = stream.next().await {
+            let resp = value?;
+            for blob in resp.blobs.blobs() {
+                container_client
+                    .blob_client(blob.name.clone())
+                    .delete()
+                    .into_future()
+                    .await?;
+                tracing::info!("Deleted blob: {}", blob.name);
+                deleted += 1;
+            }
+        }
+
+        Ok(deleted)
+    }
+
+    async fn delete_blob(&self, blob_name: &str) -> Result<(), ServiceError> {
+        let result = self.client
+            .container_client(&self.container_name)
+            .blob_client(blob_name)
+            .delete()
+            .into_future()
+            .await;
+
+        match result.map_err(ServiceError::from) {
+            Ok(_) | Err(ServiceError::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_blobs(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<String>, ServiceError> {
+        let container_client = self.client.container_client(&self.container_name);
+
+        let mut stream = container_client
+            .list_blobs()
+            .prefix(prefix.to_string())
+            .into_stream();
+
+        let mut names = Vec::new();
+        while let Some(value) = stream.next().await {
+            let resp = value?;
+            names.extend(resp.blobs.blobs().map(|blob| blob.name.clone()));
+        }
+
+        Ok(names)
+    }
+
+    async fn total_size(&self, prefix: &str) -> Result<u64, ServiceError> {
+        let container_client = self.client.container_client(&self.container_name);
+
+        let mut stream = container_client
+            .list_blobs()
+            .prefix(prefix.to_string())
+            .into_stream();
+
+        let mut total = 0;
+        while let Some(value) = stream.next().await {
+            let resp = value?;
+            total += resp.blobs.blobs().map(|blob| blob.properties.content_length).sum::<u64>();
+        }
+
+        Ok(total)
+    }
+
+    /// Server-side copies `source` to `destination` within the container and
+    /// returns the new blob's URL. Waits for the copy to finish if Azure
+    /// reports it as pending.
+    async fn copy_blob(
+        &self,
+        source: &str,
+        destination: &str,
+    ) -> Result<String, ServiceError> {
+        let container_client = self.client.container_client(&self.container_name);
+        let source_url = container_client.blob_client(source).url()?;
+        let blob_client = container_client.blob_client(destination);
+
+        let mut status = blob_client.copy(source_url).into_future().await?.copy_status;
+        for _ in 0..20 {
+            if status != CopyStatus::Pending {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            let props = blob_client.get_properties().into_future().await?;
+            status = props.blob.properties.copy_status.unwrap_or(CopyStatus::Success);
+        }
+
+        if status != CopyStatus::Success {
+            return Err(ServiceError::Storage(format!(
+                "Copy of {} to {} did not complete: {:?}", source, destination, status
+            )));
+        }
+
+        Ok(format!("https://{}.blob.core.windows.net/{}/{}",
+            self.account_name, self.container_name, destination))
+    }
+
+    async fn get_blob_info(&self, blob_name: &str) -> Result<BlobInfo, ServiceError> {
+        let props = self.client
+            .container_client(&self.container_name)
+            .blob_client(blob_name)
+            .get_properties()
+            .into_future()
+            .await?
+            .blob
+            .properties;
+
+        let etag = match &props.content_md5 {
+            Some(md5) => md5.as_slice().iter().map(|b| format!("{:02x}", b)).collect(),
+            None => props.etag.to_string().trim_matches('"').to_string(),
+        };
+
+        Ok(BlobInfo {
+            etag,
+            content_type: props.content_type,
+            content_length: props.content_length,
+        })
+    }
+
+    fn stream_blob(
+        &self,
+        blob_name: &str,
+    ) -> Result<BlobStream, ServiceError> {
+        let chunks = self.client
+            .container_client(&self.container_name)
+            .blob_client(blob_name)
+            .get()
+            .into_stream();
+
+        Ok(chunks
+            .flat_map(|chunk| match chunk {
+                Ok(resp) => resp.data.map(|data| data.map_err(ServiceError::from)).left_stream(),
+                Err(e) => stream::once(async move { Err(ServiceError::from(e)) }).right_stream(),
+            })
+            .boxed())
+    }
+
+    async fn get_blob_content(
+        &self,
+        blob_name: &str,
+    ) -> Result<Vec<u8>, ServiceError> {
+        let container_client = self.client.container_client(&self.container_name);
+        let blob_client = container_client.blob_client(blob_name);
+
+        let data = blob_client.get_content().await?;
+        Ok(data)
+    }
+}
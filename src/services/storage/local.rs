@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tokio_util::io::ReaderStream;
+use crate::config::Config;
+use crate::services::error::ServiceError;
+use crate::services::metrics::Metrics;
+use super::{BlobInfo, BlobStream, StorageBackend};
+
+// Uploads are written here first and renamed into place, so readers never
+// see half a file. Listing skips them.
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// Stores blobs as files under `LOCAL_STORAGE_DIR`. Their URLs point at
+/// `LOCAL_STORAGE_BASE_URL`, which defaults to this server's `/emotes` route.
+pub struct LocalFsBackend {
+    root: PathBuf,
+    base_url: String,
+    metrics: Arc<Metrics>,
+}
+
+impl LocalFsBackend {
+    pub fn new(cfg: &Config, metrics: Arc<Metrics>) -> Self {
+        Self {
+            root: PathBuf::from(&cfg.local_storage_dir),
+            base_url: cfg.local_storage_base_url.trim_end_matches('/').to_string(),
+            metrics,
+        }
+    }
+
+    /// Maps a blob name onto the disk, refusing anything that could leave `root`.
+    fn path_for(&self, blob_name: &str) -> Result<PathBuf, ServiceError> {
+        let mut path = self.root.clone();
+        for part in blob_name.split('/') {
+            if part.is_empty() || part == "." || part == ".." || part.contains('\\') {
+                return Err(ServiceError::Storage(format!("Invalid blob name {}", blob_name)));
+            }
+            path.push(part);
+        }
+        Ok(path)
+    }
+
+    /// Nested folders (e.g. `trending/...`) are sent as one encoded segment,
+    /// which is how the `/emotes/{folder}/{file_name}` route expects them.
+    fn url_for(&self, blob_name: &str) -> String {
+        match blob_name.rsplit_once('/') {
+            Some((folder, file_name)) => {
+                format!("{}/{}/{}", self.base_url, folder.replace('/', "%2F"), file_name)
+            }
+            None => format!("{}/{}", self.base_url, blob_name),
+        }
+    }
+
+    /// Every stored blob under `prefix` with its size, as `(name, bytes)`.
+    async fn walk(&self, prefix: &str) -> Result<Vec<(String, u64)>, ServiceError> {
+        let mut blobs = Vec::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                // Nothing has been stored yet
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                let path = entry.path();
+                if metadata.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let Some(name) = blob_name_of(&self.root, &path) else {
+                    continue;
+                };
+                if name.starts_with(prefix) && !name.ends_with(PARTIAL_SUFFIX) {
+                    blobs.push((name, metadata.len()));
+                }
+            }
+        }
+        blobs.sort();
+        Ok(blobs)
+    }
+}
+
+/// The `/`-separated blob name of a file under `root`.
+fn blob_name_of(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts = relative
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(parts.join("/"))
+}
+
+#[async_trait]
+impl StorageBackend for LocalFsBackend {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn get_container_url(&self) -> String {
+        self.base_url.clone()
+    }
+
+    /// Creates the storage directory if needed, which also proves it's writable.
+    async fn check_container(&self) -> Result<(), ServiceError> {
+        tokio::fs::create_dir_all(&self.root).await?;
+        Ok(())
+    }
+
+    async fn upload_blob(
+        &self,
+        data: Vec<u8>,
+        blob_name: &str,
+        _content_type: &str,
+        overwrite: bool,
+    ) -> Result<String, ServiceError> {
+        let path = self.path_for(blob_name)?;
+        if !overwrite && tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(self.url_for(blob_name));
+        }
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut partial = path.clone().into_os_string();
+        partial.push(PARTIAL_SUFFIX);
+        tokio::fs::write(&partial, data).await?;
+        tokio::fs::rename(&partial, &path).await?;
+        self.metrics.record_blob_upload();
+
+        Ok(self.url_for(blob_name))
+    }
+
+    async fn delete_blobs_by_prefix(&self, prefix: &str) -> Result<usize, ServiceError> {
+        let blobs = self.walk(prefix).await?;
+        for (name, _) in &blobs {
+            self.delete_blob(name).await?;
+            tracing::info!("Deleted blob: {}", name);
+        }
+        Ok(blobs.len())
+    }
+
+    async fn delete_blob(&self, blob_name: &str) -> Result<(), ServiceError> {
+        match tokio::fs::remove_file(self.path_for(blob_name)?).await.map_err(ServiceError::from) {
+            Ok(()) | Err(ServiceError::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_blobs(&self, prefix: &str) -> Result<Vec<String>, ServiceError> {
+        Ok(self.walk(prefix).await?.into_iter().map(|(name, _)| name).collect())
+    }
+
+    async fn total_size(&self, prefix: &str) -> Result<u64, ServiceError> {
+        Ok(self.walk(prefix).await?.iter().map(|(_, size)| size).sum())
+    }
+
+    async fn copy_blob(&self, source: &str, destination: &str) -> Result<String, ServiceError> {
+        let target = self.path_for(destination)?;
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(self.path_for(source)?, &target).await?;
+        Ok(self.url_for(destination))
+    }
+
+    /// Files carry no content type, so callers fall back to the extension.
+    /// The ETag is derived from the size and modification time.
+    async fn get_blob_info(&self, blob_name: &str) -> Result<BlobInfo, ServiceError> {
+        let metadata = tokio::fs::metadata(self.path_for(blob_name)?).await?;
+        if !metadata.is_file() {
+            return Err(ServiceError::NotFound);
+        }
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos());
+
+        Ok(BlobInfo {
+            etag: format!("{:x}-{:x}", metadata.len(), modified),
+            content_type: "application/octet-stream".to_string(),
+            content_length: metadata.len(),
+        })
+    }
+
+    fn stream_blob(&self, blob_name: &str) -> Result<BlobStream, ServiceError> {
+        let path = self.path_for(blob_name)?;
+        let chunks = stream::once(tokio::fs::File::open(path)).flat_map(|file| match file {
+            Ok(file) => ReaderStream::new(file).map(|chunk| chunk.map_err(ServiceError::from)).left_stream(),
+            Err(e) => stream::once(async move { Err(ServiceError::from(e)) }).right_stream(),
+        });
+        Ok(chunks.boxed())
+    }
+
+    async fn get_blob_content(&self, blob_name: &str) -> Result<Vec<u8>, ServiceError> {
+        Ok(tokio::fs::read(self.path_for(blob_name)?).await?)
+    }
+}
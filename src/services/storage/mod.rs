@@ -1,61 +1,111 @@
-use azure_storage::StorageCredentials;
-use azure_storage_blobs::prelude::*;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
 use std::sync::Arc;
-use crate::config::Config;
+use crate::config::{Config, StorageBackendKind};
+use crate::services::error::ServiceError;
+use crate::services::metrics::Metrics;
+
+mod azure;
+mod local;
+mod s3;
+pub use azure::AzureBackend;
+pub use local::LocalFsBackend;
+pub use s3::S3Backend;
+
+/// Properties needed to serve a blob over HTTP.
+#[derive(Debug, Clone)]
+pub struct BlobInfo {
+    /// Changes whenever the content does: Azure's hex MD5 (or ETag), S3's
+    /// ETag, or a size/mtime tag for local files.
+    pub etag: String,
+    pub content_type: String,
+    pub content_length: u64,
+}
+
+pub type BlobStream = BoxStream<'static, Result<Bytes, ServiceError>>;
+
+/// Where emote files live. Blob names are `/`-separated paths such as
+/// `folder/file.webp`; prefixes include the trailing `/`.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// Short name reported by the readiness probe.
+    fn name(&self) -> &'static str;
+
+    /// URL that every stored blob's URL starts with, followed by `/{blob_name}`.
+    fn get_container_url(&self) -> String;
+
+    async fn check_container(&self) -> Result<(), ServiceError>;
+
+    /// Uploads `data` and returns the blob's URL. Unless `overwrite` is set,
+    /// an existing blob is kept as-is and its URL returned without uploading.
+    async fn upload_blob(
+        &self,
+        data: Vec<u8>,
+        blob_name: &str,
+        content_type: &str,
+        overwrite: bool,
+    ) -> Result<String, ServiceError>;
 
+    async fn delete_blobs_by_prefix(&self, prefix: &str) -> Result<usize, ServiceError>;
+
+    /// Deletes a single blob. Deleting a blob that doesn't exist is not an error.
+    async fn delete_blob(&self, blob_name: &str) -> Result<(), ServiceError>;
+
+    async fn list_blobs(&self, prefix: &str) -> Result<Vec<String>, ServiceError>;
+
+    /// Sums the size of every blob under `prefix` (everything for "").
+    async fn total_size(&self, prefix: &str) -> Result<u64, ServiceError>;
+
+    /// Copies `source` to `destination` and returns the new blob's URL.
+    async fn copy_blob(&self, source: &str, destination: &str) -> Result<String, ServiceError>;
+
+    async fn get_blob_info(&self, blob_name: &str) -> Result<BlobInfo, ServiceError>;
+
+    /// Streams a blob's content chunk by chunk instead of buffering it whole.
+    fn stream_blob(&self, blob_name: &str) -> Result<BlobStream, ServiceError>;
+
+    async fn get_blob_content(&self, blob_name: &str) -> Result<Vec<u8>, ServiceError>;
+}
+
+/// The configured `StorageBackend`, or none when Azure has no usable
+/// connection string or S3 has no bucket; every call then fails with a
+/// storage error.
 pub struct StorageService {
-    client: Option<Arc<BlobServiceClient>>,
-    container_name: String,
-    account_name: String,
+    backend: Option<Box<dyn StorageBackend>>,
 }
 
 impl StorageService {
-    pub fn new(cfg: &Config) -> Self {
-        if cfg.azure_conn_str.is_empty() {
-            return Self {
-                client: None,
-                container_name: cfg.container_name.clone(),
-                account_name: String::new(),
-            };
-        }
-
-        // Simplistic connection string parsing for demo purposes
-        let account_name = cfg.azure_conn_str
-            .split(';')
-            .find(|s| s.starts_with("AccountName="))
-            .map(|s| s.trim_start_matches("AccountName=").to_string())
-            .unwrap_or_default();
-
-        let account_key = cfg.azure_conn_str
-            .split(';')
-            .find(|s| s.starts_with("AccountKey="))
-            .map(|s| s.trim_start_matches("AccountKey=").to_string())
-            .unwrap_or_default();
-
-        if account_name.is_empty() || account_key.is_empty() {
-            return Self {
-                client: None,
-                container_name: cfg.container_name.clone(),
-                account_name,
-            };
-        }
-
-        let credentials = StorageCredentials::access_key(account_name.clone(), account_key);
-        let client = BlobServiceClient::new(account_name.clone(), credentials);
-
-        Self {
-            client: Some(Arc::new(client)),
-            container_name: cfg.container_name.clone(),
-            account_name,
-        }
+    pub fn new(cfg: &Config, metrics: Arc<Metrics>) -> Self {
+        let backend: Option<Box<dyn StorageBackend>> = match cfg.storage_backend {
+            StorageBackendKind::Azure => AzureBackend::new(cfg, metrics).map(|b| Box::new(b) as _),
+            StorageBackendKind::Local => Some(Box::new(LocalFsBackend::new(cfg, metrics))),
+            StorageBackendKind::S3 => S3Backend::new(cfg, metrics).map(|b| Box::new(b) as _),
+        };
+        Self { backend }
     }
 
     pub fn is_available(&self) -> bool {
-        self.client.is_some()
+        self.backend.is_some()
+    }
+
+    fn backend(&self) -> Result<&dyn StorageBackend, ServiceError> {
+        self.backend
+            .as_deref()
+            .ok_or_else(|| ServiceError::Storage("Storage not configured".to_string()))
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.as_deref().map_or("none", |b| b.name())
     }
 
     pub fn get_container_url(&self) -> String {
-        format!("https://{}.blob.core.windows.net/{}", self.account_name, self.container_name)
+        self.backend.as_deref().map(|b| b.get_container_url()).unwrap_or_default()
+    }
+
+    /// Cheap connectivity probe for the readiness check.
+    pub async fn check_container(&self) -> Result<(), ServiceError> {
+        self.backend()?.check_container().await
     }
 
     pub async fn upload_blob(
@@ -63,66 +113,40 @@ impl StorageService {
         data: Vec<u8>,
         blob_name: &str,
         content_type: &str,
-    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.as_ref().ok_or("Azure Storage not initialized")?;
-        let container_client = client.container_client(&self.container_name);
-        let blob_client = container_client.blob_client(blob_name);
-
-        // Check if exists
-        match blob_client.get_properties().into_future().await {
-            Ok(_) => {
-                return Ok(format!("https://{}.blob.core.windows.net/{}/{}", 
-                    self.account_name, self.container_name, blob_name));
-            }
-            Err(_) => {} // Assume not found or other error
-        }
-
-        blob_client
-            .put_block_blob(data)
-            .content_type(content_type.to_string())
-            .into_future()
-            .await?;
-
-        Ok(format!("https://{}.blob.core.windows.net/{}/{}", 
-            self.account_name, self.container_name, blob_name))
+        overwrite: bool,
+    ) -> Result<String, ServiceError> {
+        self.backend()?.upload_blob(data, blob_name, content_type, overwrite).await
     }
 
-    pub async fn delete_blobs_by_prefix(
-        &self,
-        prefix: &str,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.as_ref().ok_or("Azure Storage not initialized")?;
-        let container_client = client.container_client(&self.container_name);
-
-        let mut stream = container_client
-            .list_blobs()
-            .prefix(prefix.to_string())
-            .into_stream();
-
-        while let Some(value) = futures::StreamExt::next(&mut stream).await {
-            let resp = value?;
-            for blob in resp.blobs.blobs() {
-                container_client
-                    .blob_client(blob.name.clone())
-                    .delete()
-                    .into_future()
-                    .await?;
-                tracing::info!("Deleted blob: {}", blob.name);
-            }
-        }
-
-        Ok(())
+    pub async fn delete_blobs_by_prefix(&self, prefix: &str) -> Result<usize, ServiceError> {
+        self.backend()?.delete_blobs_by_prefix(prefix).await
     }
 
-    pub async fn get_blob_content(
-        &self,
-        blob_name: &str,
-    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.as_ref().ok_or("Azure Storage not initialized")?;
-        let container_client = client.container_client(&self.container_name);
-        let blob_client = container_client.blob_client(blob_name);
+    pub async fn delete_blob(&self, blob_name: &str) -> Result<(), ServiceError> {
+        self.backend()?.delete_blob(blob_name).await
+    }
+
+    pub async fn list_blobs(&self, prefix: &str) -> Result<Vec<String>, ServiceError> {
+        self.backend()?.list_blobs(prefix).await
+    }
+
+    pub async fn total_size(&self, prefix: &str) -> Result<u64, ServiceError> {
+        self.backend()?.total_size(prefix).await
+    }
+
+    pub async fn copy_blob(&self, source: &str, destination: &str) -> Result<String, ServiceError> {
+        self.backend()?.copy_blob(source, destination).await
+    }
+
+    pub async fn get_blob_info(&self, blob_name: &str) -> Result<BlobInfo, ServiceError> {
+        self.backend()?.get_blob_info(blob_name).await
+    }
+
+    pub fn stream_blob(&self, blob_name: &str) -> Result<BlobStream, ServiceError> {
+        self.backend()?.stream_blob(blob_name)
+    }
 
-        let data = blob_client.get_content().await?;
-        Ok(data)
+    pub async fn get_blob_content(&self, blob_name: &str) -> Result<Vec<u8>, ServiceError> {
+        self.backend()?.get_blob_content(blob_name).await
     }
 }
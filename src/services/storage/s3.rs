@@ -0,0 +1,279 @@
+use async_trait::async_trait;
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_s3::error::{DisplayErrorContext, SdkError};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{Delete, ObjectIdentifier};
+use aws_sdk_s3::Client;
+use futures::stream::{self, StreamExt};
+use std::sync::Arc;
+use tokio_util::io::ReaderStream;
+use crate::config::Config;
+use crate::services::error::ServiceError;
+use crate::services::metrics::Metrics;
+use super::{BlobInfo, BlobStream, StorageBackend};
+
+// DeleteObjects and ListObjectsV2 both stop at 1000 keys per call
+const MAX_KEYS_PER_REQUEST: usize = 1000;
+
+/// AWS S3 or an S3-compatible store such as MinIO, configured from the
+/// `S3_*` variables.
+pub struct S3Backend {
+    client: Client,
+    bucket: String,
+    /// Public URL of the bucket; blob URLs are this plus `/{key}`.
+    bucket_url: String,
+    metrics: Arc<Metrics>,
+}
+
+impl S3Backend {
+    /// `None` when `S3_BUCKET` is empty.
+    pub fn new(cfg: &Config, metrics: Arc<Metrics>) -> Option<Self> {
+        let bucket = cfg.s3_bucket.trim().to_string();
+        if bucket.is_empty() {
+            return None;
+        }
+        let endpoint = cfg.s3_endpoint.trim().trim_end_matches('/');
+
+        let mut builder = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(cfg.s3_region.clone()));
+        if !cfg.s3_access_key_id.is_empty() && !cfg.s3_secret_access_key.is_empty() {
+            builder = builder.credentials_provider(Credentials::new(
+                cfg.s3_access_key_id.clone(),
+                cfg.s3_secret_access_key.clone(),
+                None,
+                None,
+                "environment",
+            ));
+        } else {
+            tracing::warn!("S3_ACCESS_KEY_ID or S3_SECRET_ACCESS_KEY is not set; S3 requests will fail");
+        }
+
+        // Custom endpoints (MinIO and most S3 clones) only support path-style URLs
+        let bucket_url = if endpoint.is_empty() {
+            format!("https://{}.s3.{}.amazonaws.com", bucket, cfg.s3_region)
+        } else {
+            builder = builder.endpoint_url(endpoint).force_path_style(true);
+            format!("{}/{}", endpoint, bucket)
+        };
+
+        Some(Self {
+            client: Client::from_conf(builder.build()),
+            bucket,
+            bucket_url,
+            metrics,
+        })
+    }
+
+    fn url_for(&self, key: &str) -> String {
+        format!("{}/{}", self.bucket_url, key)
+    }
+
+    /// Every object under `prefix` with its size, following continuation
+    /// tokens past the 1000-key page limit.
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<(String, u64)>, ServiceError> {
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self.client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(s3_error)?;
+
+            objects.extend(page.contents().iter().filter_map(|object| {
+                let size = object.size().unwrap_or(0).max(0) as u64;
+                object.key().map(|key| (key.to_string(), size))
+            }));
+
+            match page.next_continuation_token() {
+                Some(token) if page.is_truncated().unwrap_or(false) => continuation_token = Some(token.to_string()),
+                _ => break,
+            }
+        }
+        Ok(objects)
+    }
+}
+
+/// 404s become `NotFound` so callers can tell a missing blob from a failure.
+fn s3_error<E>(e: SdkError<E>) -> ServiceError
+where
+    E: std::error::Error + 'static,
+{
+    if e.raw_response().is_some_and(|r| r.status().as_u16() == 404) {
+        return ServiceError::NotFound;
+    }
+    ServiceError::Storage(DisplayErrorContext(&e).to_string())
+}
+
+/// Percent-encodes a key for `x-amz-copy-source`, keeping `/` as is.
+fn encode_copy_source(bucket: &str, key: &str) -> String {
+    let mut encoded = format!("{}/", bucket);
+    for b in key.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~' | b'/') {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+    encoded
+}
+
+#[async_trait]
+impl StorageBackend for S3Backend {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    fn get_container_url(&self) -> String {
+        self.bucket_url.clone()
+    }
+
+    /// Cheap connectivity probe: a HEAD on the bucket.
+    async fn check_container(&self) -> Result<(), ServiceError> {
+        self.client.head_bucket().bucket(&self.bucket).send().await.map_err(s3_error)?;
+        Ok(())
+    }
+
+    async fn upload_blob(
+        &self,
+        data: Vec<u8>,
+        blob_name: &str,
+        content_type: &str,
+        overwrite: bool,
+    ) -> Result<String, ServiceError> {
+        // Any error is assumed to mean "not found"
+        if !overwrite && self.client.head_object().bucket(&self.bucket).key(blob_name).send().await.is_ok() {
+            return Ok(self.url_for(blob_name));
+        }
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(blob_name)
+            .content_type(content_type)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(s3_error)?;
+        self.metrics.record_blob_upload();
+
+        Ok(self.url_for(blob_name))
+    }
+
+    async fn delete_blobs_by_prefix(&self, prefix: &str) -> Result<usize, ServiceError> {
+        let keys: Vec<String> = self.list_objects(prefix).await?.into_iter().map(|(key, _)| key).collect();
+
+        for chunk in keys.chunks(MAX_KEYS_PER_REQUEST) {
+            let objects = chunk
+                .iter()
+                .map(|key| ObjectIdentifier::builder().key(key).build())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| ServiceError::Storage(e.to_string()))?;
+            let delete = Delete::builder()
+                .set_objects(Some(objects))
+                .quiet(true)
+                .build()
+                .map_err(|e| ServiceError::Storage(e.to_string()))?;
+
+            let output = self.client
+                .delete_objects()
+                .bucket(&self.bucket)
+                .delete(delete)
+                .send()
+                .await
+                .map_err(s3_error)?;
+
+            // A batch delete reports per-key failures in the body, not the status
+            if let Some(failed) = output.errors().first() {
+                return Err(ServiceError::Storage(format!(
+                    "Failed to delete {}: {}",
+                    failed.key().unwrap_or_default(),
+                    failed.message().unwrap_or_default()
+                )));
+            }
+            for key in chunk {
+                tracing::info!("Deleted blob: {}", key);
+            }
+        }
+
+        Ok(keys.len())
+    }
+
+    /// S3 deletes are idempotent, so a missing key already succeeds.
+    async fn delete_blob(&self, blob_name: &str) -> Result<(), ServiceError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(blob_name)
+            .send()
+            .await
+            .map_err(s3_error)?;
+        Ok(())
+    }
+
+    async fn list_blobs(&self, prefix: &str) -> Result<Vec<String>, ServiceError> {
+        Ok(self.list_objects(prefix).await?.into_iter().map(|(key, _)| key).collect())
+    }
+
+    async fn total_size(&self, prefix: &str) -> Result<u64, ServiceError> {
+        Ok(self.list_objects(prefix).await?.iter().map(|(_, size)| size).sum())
+    }
+
+    async fn copy_blob(&self, source: &str, destination: &str) -> Result<String, ServiceError> {
+        self.client
+            .copy_object()
+            .bucket(&self.bucket)
+            .copy_source(encode_copy_source(&self.bucket, source))
+            .key(destination)
+            .send()
+            .await
+            .map_err(s3_error)?;
+        Ok(self.url_for(destination))
+    }
+
+    async fn get_blob_info(&self, blob_name: &str) -> Result<BlobInfo, ServiceError> {
+        let head = self.client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(blob_name)
+            .send()
+            .await
+            .map_err(s3_error)?;
+
+        Ok(BlobInfo {
+            etag: head.e_tag().unwrap_or_default().trim_matches('"').to_string(),
+            content_type: head.content_type().unwrap_or_default().to_string(),
+            content_length: head.content_length().unwrap_or(0).max(0) as u64,
+        })
+    }
+
+    fn stream_blob(&self, blob_name: &str) -> Result<BlobStream, ServiceError> {
+        let request = self.client.get_object().bucket(&self.bucket).key(blob_name);
+        let chunks = stream::once(request.send()).flat_map(|object| match object {
+            Ok(object) => ReaderStream::new(object.body.into_async_read())
+                .map(|chunk| chunk.map_err(ServiceError::from))
+                .left_stream(),
+            Err(e) => stream::once(async move { Err(s3_error(e)) }).right_stream(),
+        });
+        Ok(chunks.boxed())
+    }
+
+    async fn get_blob_content(&self, blob_name: &str) -> Result<Vec<u8>, ServiceError> {
+        let object = self.client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(blob_name)
+            .send()
+            .await
+            .map_err(s3_error)?;
+        let data = object.body
+            .collect()
+            .await
+            .map_err(|e| ServiceError::Storage(e.to_string()))?;
+        Ok(data.into_bytes().to_vec())
+    }
+}